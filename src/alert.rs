@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use futures_util::{future, stream, Stream, StreamExt, TryStreamExt};
 use http::Method;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::{skip_serializing_none, DeserializeFromStr, SerializeDisplay};
@@ -41,6 +42,10 @@ pub struct AlertValue {
     #[builder(default, setter(strip_option))]
     #[serde(default, with = "chrono::serde::ts_seconds_option")]
     pub closed_at: Option<DateTime<Utc>>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Alert status
@@ -65,6 +70,44 @@ impl AlertStatus {
     }
 }
 
+/// An alert log entity
+pub type AlertLog = Entity<AlertLogValue>;
+
+/// An alert log id
+pub type AlertLogId = Id<AlertLogValue>;
+
+/// An alert log value
+#[skip_serializing_none]
+#[derive(PartialEq, Clone, Debug, TypedBuilder, Serialize, Deserialize)]
+#[builder(field_defaults(setter(into)))]
+#[serde(rename_all = "camelCase")]
+pub struct AlertLogValue {
+    pub status: AlertStatus,
+    pub trigger: AlertLogTrigger,
+    #[builder(default, setter(strip_option))]
+    pub monitor_id: Option<MonitorId>,
+    #[builder(default, setter(strip_option))]
+    pub target_value: Option<f64>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Alert log trigger
+#[derive(
+    PartialEq, Eq, Copy, Clone, Debug, Display, EnumString, SerializeDisplay, DeserializeFromStr,
+)]
+#[strum(serialize_all = "camelCase")]
+pub enum AlertLogTrigger {
+    Monitoring,
+    Manual,
+    MonitorDelete,
+    HostRetire,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +182,83 @@ mod tests {
         assert_eq!(alert, serde_json::from_value(json).unwrap());
     }
 
+    fn alert_log_example1() -> AlertLog {
+        AlertLog::builder()
+            .id("log1")
+            .value(
+                AlertLogValue::builder()
+                    .status(AlertStatus::Critical)
+                    .trigger(AlertLogTrigger::Monitoring)
+                    .monitor_id("monitor1")
+                    .target_value(95.0)
+                    .created_at(DateTime::from_timestamp(1690000000, 0).unwrap())
+                    .build(),
+            )
+            .build()
+    }
+
+    fn alert_log_json_example1() -> serde_json::Value {
+        json!({
+            "id": "log1",
+            "status": "CRITICAL",
+            "trigger": "monitoring",
+            "monitorId": "monitor1",
+            "targetValue": 95.0,
+            "createdAt": 1690000000,
+        })
+    }
+
+    fn alert_log_example2() -> AlertLog {
+        AlertLog::builder()
+            .id("log2")
+            .value(
+                AlertLogValue::builder()
+                    .status(AlertStatus::Ok)
+                    .trigger(AlertLogTrigger::Manual)
+                    .created_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+                    .build(),
+            )
+            .build()
+    }
+
+    fn alert_log_json_example2() -> serde_json::Value {
+        json!({
+            "id": "log2",
+            "status": "OK",
+            "trigger": "manual",
+            "createdAt": 1700000000,
+        })
+    }
+
+    #[rstest]
+    #[case(alert_log_example1(), alert_log_json_example1())]
+    #[case(alert_log_example2(), alert_log_json_example2())]
+    fn test_alert_log_json(#[case] alert_log: AlertLog, #[case] json: serde_json::Value) {
+        assert_eq!(serde_json::to_value(&alert_log).unwrap(), json);
+        assert_eq!(alert_log, serde_json::from_value(json).unwrap());
+    }
+
+    #[rstest]
+    #[case(AlertLogTrigger::Monitoring, "monitoring")]
+    #[case(AlertLogTrigger::Manual, "manual")]
+    #[case(AlertLogTrigger::MonitorDelete, "monitorDelete")]
+    #[case(AlertLogTrigger::HostRetire, "hostRetire")]
+    fn test_alert_log_trigger(
+        #[case] alert_log_trigger: AlertLogTrigger,
+        #[case] alert_log_trigger_str: &str,
+    ) {
+        assert_eq!(alert_log_trigger.to_string(), alert_log_trigger_str);
+        assert_eq!(alert_log_trigger, alert_log_trigger_str.parse().unwrap());
+        assert_eq!(
+            alert_log_trigger,
+            serde_json::from_value(alert_log_trigger_str.into()).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(alert_log_trigger).unwrap(),
+            alert_log_trigger_str
+        );
+    }
+
     #[rstest]
     #[case(AlertStatus::Ok, "OK")]
     #[case(AlertStatus::Critical, "CRITICAL")]
@@ -158,27 +278,160 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches open alerts.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alerts#list>.
-    pub async fn list_open_alerts(
-        &self,
-        cursor_opt: Option<impl Into<AlertId>>,
-        limit: u8,
-    ) -> Result<(Vec<Alert>, Option<AlertId>)> {
-        self.list_alerts("", cursor_opt, limit).await
+/// The maximum number of alerts fetched at once.
+const ALERTS_PAGE_LIMIT: u8 = 100;
+
+client_methods! {
+    impl Client {
+        /// Fetches open alerts.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_open_alerts(
+            &self,
+            cursor_opt: Option<impl Into<AlertId>>,
+            limit: u8,
+        ) -> Result<(Vec<Alert>, Option<AlertId>)> {
+            self.list_alerts("", cursor_opt, limit).await
+        }
+
+        /// Fetches all (open or closed) alerts.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_all_alerts(
+            &self,
+            cursor_opt: Option<impl Into<AlertId>>,
+            limit: u8,
+        ) -> Result<(Vec<Alert>, Option<AlertId>)> {
+            self.list_alerts("true", cursor_opt, limit).await
+        }
+
+        /// Collects all (open or closed) alerts opened at or after `since`.
+        /// Fetching stops at the first alert opened before `since`.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn collect_alerts_since(
+            &self,
+            since: impl Into<DateTime<Utc>>,
+        ) -> Result<Vec<Alert>> {
+            let since = since.into();
+            self.alerts_stream(true)
+                .try_take_while(|alert| future::ok(alert.opened_at >= since))
+                .try_collect()
+                .await
+        }
+
+        /// Gets an alert.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(alert_id)))]
+        pub async fn get_alert(&self, alert_id: impl Into<AlertId>) -> Result<Alert> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/alerts/{}", alert_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Fetches the status transition logs of an alert.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#logs>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(alert_id)))]
+        pub async fn list_alert_logs(
+            &self,
+            alert_id: impl Into<AlertId>,
+            cursor_opt: Option<impl Into<AlertLogId>>,
+            limit: u8,
+        ) -> Result<(Vec<AlertLog>, Option<AlertLogId>)> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/alerts/{}/logs", alert_id),
+                query_params! {
+                    nextId = *cursor_opt.map(Into::into).as_deref().unwrap_or_default(),
+                    limit = limit.to_string(),
+                },
+                request_body![],
+                response_body! { logs: Vec<AlertLog>, nextId: Option<AlertLogId> },
+            )
+            .await
+        }
+
+        /// Updates an alert.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#update>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(alert_id)))]
+        pub async fn update_alert(
+            &self,
+            alert_id: impl Into<AlertId>,
+            memo: impl AsRef<str>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/alerts/{}", alert_id),
+                query_params![],
+                request_body! { memo: String = memo.as_ref().to_owned() },
+                response_body!(),
+            )
+            .await
+        }
+
+        /// Closes the specified alert.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alerts#close>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(alert_id)))]
+        pub async fn close_alert(
+            &self,
+            alert_id: impl Into<AlertId>,
+            reason: impl AsRef<str>,
+        ) -> Result<Alert> {
+            self.request(
+                Method::POST,
+                format_url!("/api/v0/alerts/{}/close", alert_id),
+                query_params![],
+                request_body! { reason: String = reason.as_ref().to_owned() },
+                response_body!(..),
+            )
+            .await
+        }
     }
+}
 
-    /// Fetches all (open or closed) alerts.
+impl Client {
+    /// Returns a stream of open alerts, or all the alerts if `with_closed` is true.
+    /// The stream follows the cursor lazily.
+    /// ```rust,no_run
+    /// use futures_util::TryStreamExt;
+    /// use mackerel_client::Client;
+    ///
+    /// # #[async_std::main]
+    /// # async fn main() -> mackerel_client::error::Result<()> {
+    /// let client = Client::new("<Mackerel-API-KEY>");
+    /// let alerts = client.alerts_stream(false).try_collect::<Vec<_>>().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
     /// See <https://mackerel.io/api-docs/entry/alerts#list>.
-    pub async fn list_all_alerts(
-        &self,
-        cursor_opt: Option<impl Into<AlertId>>,
-        limit: u8,
-    ) -> Result<(Vec<Alert>, Option<AlertId>)> {
-        self.list_alerts("true", cursor_opt, limit).await
+    pub fn alerts_stream(&self, with_closed: bool) -> impl Stream<Item = Result<Alert>> + '_ {
+        let with_closed = if with_closed { "true" } else { "" };
+        stream::try_unfold(Some(None), move |cursor_opt_opt| async move {
+            let Some(cursor_opt) = cursor_opt_opt else {
+                return Result::Ok(None);
+            };
+            let (alerts, next_id_opt) = self
+                .list_alerts(with_closed, cursor_opt, ALERTS_PAGE_LIMIT)
+                .await?;
+            Ok(Some((
+                stream::iter(alerts).map(Ok),
+                next_id_opt.map(Some::<AlertId>),
+            )))
+        })
+        .try_flatten()
     }
 
     async fn list_alerts(
@@ -200,59 +453,18 @@ impl Client {
         )
         .await
     }
+}
 
-    /// Gets an alert.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alerts#get>.
-    pub async fn get_alert(&self, alert_id: impl Into<AlertId>) -> Result<Alert> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/alerts/{}", alert_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Updates an alert.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alerts#update>.
-    pub async fn update_alert(
-        &self,
-        alert_id: impl Into<AlertId>,
-        memo: impl AsRef<str>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/alerts/{}", alert_id),
-            query_params![],
-            request_body! { memo: String = memo.as_ref().to_owned() },
-            response_body!(),
-        )
-        .await
-    }
-
-    /// Closes the specified alert.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alerts#close>.
-    pub async fn close_alert(
-        &self,
-        alert_id: impl Into<AlertId>,
-        reason: impl AsRef<str>,
-    ) -> Result<Alert> {
-        self.request(
-            Method::POST,
-            format_url!("/api/v0/alerts/{}/close", alert_id),
-            query_params![],
-            request_body! { reason: String = reason.as_ref().to_owned() },
-            response_body!(..),
-        )
-        .await
+#[cfg(feature = "blocking")]
+impl crate::blocking::Client {
+    /// Blocking version of [`Client::alerts_stream()`](crate::Client::alerts_stream),
+    /// which returns an iterator following the cursor lazily.
+    pub fn alerts_stream(&self, with_closed: bool) -> impl Iterator<Item = Result<Alert>> + '_ {
+        futures_executor::block_on_stream(Box::pin(self.client.alerts_stream(with_closed)))
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -328,6 +540,71 @@ mod client_tests {
         );
     }
 
+    fn alerts_stream_server() -> httptest::ServerHandle<'static> {
+        use httptest::{all_of, matchers::*, responders::*, Expectation};
+        let alert_json = |id: &str, opened_at: i64| {
+            let mut json = entity_json_example();
+            json["id"] = json!(id);
+            json["openedAt"] = json!(opened_at);
+            json
+        };
+        let server = TEST_SERVER_POOL.get_server();
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("GET", "/api/v0/alerts"),
+                request::query(url_decoded(all_of![
+                    contains(("withClosed", "true")),
+                    contains(("limit", "100")),
+                    not(contains(key("nextId"))),
+                ])),
+            ])
+            .respond_with(json_encoded(json!({
+                "alerts": [alert_json("alert3", 1698890400), alert_json("alert2", 1698880000)],
+                "nextId": "alert2",
+            }))),
+        );
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("GET", "/api/v0/alerts"),
+                request::query(url_decoded(all_of![
+                    contains(("withClosed", "true")),
+                    contains(("limit", "100")),
+                    contains(("nextId", "alert2")),
+                ])),
+            ])
+            .times(0..)
+            .respond_with(json_encoded(json!({
+                "alerts": [alert_json("alert1", 1698870000)],
+            }))),
+        );
+        server
+    }
+
+    #[async_std::test]
+    async fn alerts_stream() {
+        let server = alerts_stream_server();
+        assert_eq!(
+            test_client!(server)
+                .alerts_stream(true)
+                .map_ok(|alert| alert.id)
+                .try_collect::<Vec<_>>()
+                .await,
+            Ok(vec!["alert3".into(), "alert2".into(), "alert1".into()]),
+        );
+    }
+
+    #[async_std::test]
+    async fn collect_alerts_since() {
+        let server = alerts_stream_server();
+        assert_eq!(
+            test_client!(server)
+                .collect_alerts_since(DateTime::from_timestamp(1698880000, 0).unwrap())
+                .await
+                .map(|alerts| alerts.into_iter().map(|alert| alert.id).collect::<Vec<_>>()),
+            Ok(vec!["alert3".into(), "alert2".into()]),
+        );
+    }
+
     #[async_std::test]
     async fn get_alert() {
         let server = test_server! {
@@ -347,6 +624,48 @@ mod client_tests {
         );
     }
 
+    #[async_std::test]
+    async fn list_alert_logs() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/alerts/alert1/logs",
+            query_params = "limit=1&nextId=log1",
+            response = json!({
+                "logs": [{
+                    "id": "log2",
+                    "status": "OK",
+                    "trigger": "monitoring",
+                    "monitorId": "monitor1",
+                    "targetValue": 10.0,
+                    "createdAt": 1698894000,
+                }],
+                "nextId": "log3",
+            }),
+        };
+        let alert_log = AlertLog::new(
+            "log2",
+            AlertLogValue::builder()
+                .status(AlertStatus::Ok)
+                .trigger(AlertLogTrigger::Monitoring)
+                .monitor_id("monitor1")
+                .target_value(10.0)
+                .created_at(DateTime::from_timestamp(1698894000, 0).unwrap())
+                .build(),
+        );
+        assert_eq!(
+            test_client!(server)
+                .list_alert_logs("alert1", Some("log1"), 1)
+                .await,
+            Ok((vec![alert_log.clone()], Some("log3".into()))),
+        );
+        assert_eq!(
+            test_client!(server)
+                .list_alert_logs(AlertId::from("alert1"), Some(AlertLogId::from("log1")), 1)
+                .await,
+            Ok((vec![alert_log], Some(AlertLogId::from("log3")))),
+        );
+    }
+
     #[async_std::test]
     async fn update_alert() {
         let server = test_server! {
@@ -49,6 +49,10 @@ pub struct AlertGroupSettingValue {
     #[builder(default, setter(!into, strip_option))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notification_interval: Option<u64>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -115,92 +119,108 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the alert group settings.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alert-group-settings#list>.
-    pub async fn list_alert_group_settings(&self) -> Result<Vec<AlertGroupSetting>> {
-        self.request(
-            Method::GET,
-            "/api/v0/alert-group-settings",
-            query_params![],
-            request_body![],
-            response_body! { alertGroupSettings: Vec<AlertGroupSetting> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the alert group settings.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alert-group-settings#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_alert_group_settings(&self) -> Result<Vec<AlertGroupSetting>> {
+            self.request(
+                Method::GET,
+                "/api/v0/alert-group-settings",
+                query_params![],
+                request_body![],
+                response_body! { alertGroupSettings: Vec<AlertGroupSetting> },
+            )
+            .await
+        }
 
-    /// Creates a new alert group setting.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alert-group-settings#create>.
-    pub async fn create_alert_group_setting(
-        &self,
-        alert_group_setting_value: impl Borrow<AlertGroupSettingValue>,
-    ) -> Result<AlertGroupSetting> {
-        self.request(
-            Method::POST,
-            "/api/v0/alert-group-settings",
-            query_params![],
-            request_body!(alert_group_setting_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new alert group setting.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alert-group-settings#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_alert_group_setting(
+            &self,
+            alert_group_setting_value: impl Borrow<AlertGroupSettingValue>,
+        ) -> Result<AlertGroupSetting> {
+            self.request(
+                Method::POST,
+                "/api/v0/alert-group-settings",
+                query_params![],
+                request_body!(alert_group_setting_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Gets an alert group setting.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alert-group-settings#get>.
-    pub async fn get_alert_group_setting(
-        &self,
-        alert_group_setting_id: impl Into<AlertGroupSettingId>,
-    ) -> Result<AlertGroupSetting> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Gets an alert group setting.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alert-group-settings#get>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(alert_group_setting_id))
+        )]
+        pub async fn get_alert_group_setting(
+            &self,
+            alert_group_setting_id: impl Into<AlertGroupSettingId>,
+        ) -> Result<AlertGroupSetting> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Updates an alert group setting.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alert-group-settings#update>.
-    pub async fn update_alert_group_setting(
-        &self,
-        alert_group_setting_id: impl Into<AlertGroupSettingId>,
-        alert_group_setting_value: impl Borrow<AlertGroupSettingValue>,
-    ) -> Result<AlertGroupSetting> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
-            query_params![],
-            request_body!(alert_group_setting_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Updates an alert group setting.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alert-group-settings#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(alert_group_setting_id))
+        )]
+        pub async fn update_alert_group_setting(
+            &self,
+            alert_group_setting_id: impl Into<AlertGroupSettingId>,
+            alert_group_setting_value: impl Borrow<AlertGroupSettingValue>,
+        ) -> Result<AlertGroupSetting> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
+                query_params![],
+                request_body!(alert_group_setting_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes an alert group setting.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/alert-group-settings#delete>.
-    pub async fn delete_alert_group_setting(
-        &self,
-        alert_group_setting_id: impl Into<AlertGroupSettingId>,
-    ) -> Result<AlertGroupSetting> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Deletes an alert group setting.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/alert-group-settings#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(alert_group_setting_id))
+        )]
+        pub async fn delete_alert_group_setting(
+            &self,
+            alert_group_setting_id: impl Into<AlertGroupSettingId>,
+        ) -> Result<AlertGroupSetting> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/alert-group-settings/{}", alert_group_setting_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -45,6 +45,10 @@ pub struct AWSIntegrationValue {
     #[builder(default)]
     #[serde(default)]
     pub services: HashMap<AWSServiceName, AWSServiceConfig>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An AWS integration service config
@@ -271,122 +275,140 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the AWS integration settings.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#list>.
-    pub async fn list_aws_integrations(&self) -> Result<Vec<AWSIntegration>> {
-        self.request(
-            Method::GET,
-            "/api/v0/aws-integrations",
-            query_params![],
-            request_body![],
-            response_body! { aws_integrations: Vec<AWSIntegration> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the AWS integration settings.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_aws_integrations(&self) -> Result<Vec<AWSIntegration>> {
+            self.request(
+                Method::GET,
+                "/api/v0/aws-integrations",
+                query_params![],
+                request_body![],
+                response_body! { aws_integrations: Vec<AWSIntegration> },
+            )
+            .await
+        }
 
-    /// Creates a new AWS integration.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#create>.
-    pub async fn create_aws_integration(
-        &self,
-        aws_integration_value: impl Borrow<AWSIntegrationValue>,
-    ) -> Result<AWSIntegration> {
-        self.request(
-            Method::POST,
-            "/api/v0/aws-integrations",
-            query_params![],
-            request_body!(aws_integration_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new AWS integration.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_aws_integration(
+            &self,
+            aws_integration_value: impl Borrow<AWSIntegrationValue>,
+        ) -> Result<AWSIntegration> {
+            self.request(
+                Method::POST,
+                "/api/v0/aws-integrations",
+                query_params![],
+                request_body!(aws_integration_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Gets an AWS integration.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#get>.
-    pub async fn get_aws_integration(
-        &self,
-        aws_integration_id: impl Into<AWSIntegrationId>,
-    ) -> Result<AWSIntegration> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Gets an AWS integration.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#get>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(aws_integration_id))
+        )]
+        pub async fn get_aws_integration(
+            &self,
+            aws_integration_id: impl Into<AWSIntegrationId>,
+        ) -> Result<AWSIntegration> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Updates an AWS integration.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#update>.
-    pub async fn update_aws_integration(
-        &self,
-        aws_integration_id: impl Into<AWSIntegrationId>,
-        aws_integration_value: impl Borrow<AWSIntegrationValue>,
-    ) -> Result<AWSIntegration> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
-            query_params![],
-            request_body!(aws_integration_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Updates an AWS integration.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(aws_integration_id))
+        )]
+        pub async fn update_aws_integration(
+            &self,
+            aws_integration_id: impl Into<AWSIntegrationId>,
+            aws_integration_value: impl Borrow<AWSIntegrationValue>,
+        ) -> Result<AWSIntegration> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
+                query_params![],
+                request_body!(aws_integration_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes an AWS integration.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#delete>.
-    pub async fn delete_aws_integration(
-        &self,
-        aws_integration_id: impl Into<AWSIntegrationId>,
-    ) -> Result<AWSIntegration> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Deletes an AWS integration.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(aws_integration_id))
+        )]
+        pub async fn delete_aws_integration(
+            &self,
+            aws_integration_id: impl Into<AWSIntegrationId>,
+        ) -> Result<AWSIntegration> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/aws-integrations/{}", aws_integration_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Generates AWS integration external ID.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#generate-external-id>.
-    pub async fn create_aws_integration_external_id(&self) -> Result<String> {
-        self.request(
-            Method::POST,
-            "/api/v0/aws-integrations-external-id",
-            query_params![],
-            request_body![],
-            response_body! { externalId: String },
-        )
-        .await
-    }
+        /// Generates AWS integration external ID.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#generate-external-id>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_aws_integration_external_id(&self) -> Result<String> {
+            self.request(
+                Method::POST,
+                "/api/v0/aws-integrations-external-id",
+                query_params![],
+                request_body![],
+                response_body! { externalId: String },
+            )
+            .await
+        }
 
-    /// Lists includable/excludable metrics for AWS integration.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/aws-integration#excludable-metrics>.
-    pub async fn list_aws_integration_metrics(
-        &self,
-    ) -> Result<HashMap<AWSServiceName, Vec<String>>> {
-        self.request(
-            Method::GET,
-            "/api/v0/aws-integrations-excludable-metrics",
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Lists includable/excludable metrics for AWS integration.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/aws-integration#excludable-metrics>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_aws_integration_metrics(
+            &self,
+        ) -> Result<HashMap<AWSServiceName, Vec<String>>> {
+            self.request(
+                Method::GET,
+                "/api/v0/aws-integrations-excludable-metrics",
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -0,0 +1,130 @@
+//! Blocking API client for Mackerel
+//!
+//! The blocking client has the same methods as [`crate::Client`],
+//! and runs each request to completion on the current thread.
+//!
+//! Do not call the methods from an async runtime, where blocking the thread stalls the other tasks.
+//! The default transport based on [`reqwest::blocking::Client`] panics inside a tokio runtime;
+//! use [`crate::Client`] in async code instead.
+//!
+//! ```rust,no_run
+//! use mackerel_client::blocking::Client;
+//!
+//! let client = Client::new("<Mackerel-API-KEY>");
+//!
+//! println!("{:?}", client.get_organization());
+//! println!("{:?}", client.list_hosts(()));
+//! ```
+use crate::error::{ConfigError, TransportError};
+use crate::transport::{Transport, TransportFuture};
+
+/// A blocking API client for Mackerel.
+#[derive(Debug)]
+pub struct Client {
+    pub(crate) client: crate::Client,
+}
+
+impl Client {
+    /// Creates a new blocking API client from API key.
+    /// ```rust
+    /// use mackerel_client::blocking::Client;
+    ///
+    /// let client = Client::new("<Mackerel-API-KEY>");
+    /// ```
+    ///
+    /// If you want to configure the client, use [`ClientBuilder::build_blocking()`](crate::client::ClientBuilder::build_blocking).
+    /// ```rust
+    /// use mackerel_client::Client;
+    /// use std::time::Duration;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("<Mackerel-API-KEY>")
+    ///     .timeout(Duration::from_secs(60))
+    ///     .build_blocking();
+    /// ```
+    pub fn new(api_key: impl AsRef<str>) -> Client {
+        crate::Client::builder().api_key(api_key).build_blocking()
+    }
+
+    /// Creates a new blocking API client from API key, or returns [`ConfigError`] if the API key is invalid.
+    pub fn try_new(api_key: impl AsRef<str>) -> Result<Client, ConfigError> {
+        crate::Client::builder()
+            .api_key(api_key)
+            .try_build_blocking()
+    }
+
+    /// Creates a new blocking API client from the environment variables `MACKEREL_APIKEY` and `MACKEREL_APIBASE`.
+    pub fn from_env() -> Result<Client, ConfigError> {
+        crate::config::ClientConfig::from_env()
+            .builder()?
+            .try_build_blocking()
+    }
+
+    pub(crate) fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+        futures_executor::block_on(future)
+    }
+}
+
+/// The default transport of the blocking client based on [`reqwest::blocking::Client`].
+///
+/// # Panics
+///
+/// Sending a request panics when called inside a tokio runtime.
+#[derive(Clone, Debug)]
+pub struct BlockingReqwestTransport(reqwest::blocking::Client);
+
+impl BlockingReqwestTransport {
+    /// Creates a new [`BlockingReqwestTransport`] from [`reqwest::blocking::Client`].
+    ///
+    /// Note that the client should not follow redirects, because the API key is sent on each request.
+    pub fn new(client: reqwest::blocking::Client) -> Self {
+        Self(client)
+    }
+}
+
+impl Transport for BlockingReqwestTransport {
+    fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+        let result = (|| {
+            let (parts, body) = request.into_parts();
+            let url = reqwest::Url::parse(&parts.uri.to_string()).map_err(TransportError::other)?;
+            let response = self
+                .0
+                .request(parts.method, url)
+                .headers(parts.headers)
+                .body(body)
+                .send()?;
+            let mut builder = http::Response::builder()
+                .status(response.status())
+                .version(response.version());
+            if let Some(headers) = builder.headers_mut() {
+                headers.extend(response.headers().clone());
+            }
+            let body = response.bytes()?;
+            builder.body(body.to_vec()).map_err(TransportError::other)
+        })();
+        Box::pin(std::future::ready(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::organization::Organization;
+    use crate::tests::*;
+
+    #[test]
+    fn blocking_client() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/org",
+            response = json!({ "name": "example" }),
+        };
+        let client = crate::Client::builder()
+            .api_key("")
+            .api_base(server.url_str("/"))
+            .build_blocking();
+        assert_eq!(
+            client.get_organization(),
+            Ok(Organization::builder().name("example").build()),
+        );
+    }
+}
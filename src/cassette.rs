@@ -0,0 +1,288 @@
+//! Record-and-replay transport of the API client for offline tests
+//!
+//! A cassette is a JSON file of the request/response pairs.
+//! Record the interactions with the real API once, and replay them without network access.
+//! ```rust,no_run
+//! use mackerel_client::Client;
+//!
+//! # #[async_std::main]
+//! # async fn main() -> mackerel_client::error::Result<()> {
+//! // Records the interactions with the API into the cassette.
+//! let client = Client::builder()
+//!     .api_key("<Mackerel-API-KEY>")
+//!     .record_cassette("tests/cassettes/list_hosts.json")
+//!     .build();
+//! let hosts = client.list_hosts(()).await?;
+//!
+//! // Replays the recorded interactions without network access.
+//! let client = Client::builder()
+//!     .api_key("<Mackerel-API-KEY>")
+//!     .replay_cassette("tests/cassettes/list_hosts.json")
+//!     .build();
+//! assert_eq!(client.list_hosts(()).await?, hosts);
+//! # Ok(())
+//! # }
+//! ```
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::error::{ConfigError, TransportError};
+use crate::transport::{Transport, TransportFuture};
+
+/// The mode of the cassette configured on [`ClientBuilder`](crate::client::ClientBuilder).
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub(crate) enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// A cassette of the recorded interactions.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// A pair of the recorded request and response.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A recorded request, where the `x-api-key` header is scrubbed.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The path and query of the request.
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+/// A recorded response.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+const SCRUBBED_HEADERS: &[&str] = &["x-api-key", "authorization"];
+
+fn recorded_headers(headers: &http::HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SCRUBBED_HEADERS.contains(&name.as_str()) {
+                "<scrubbed>".to_owned()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.as_str().to_owned(), value)
+        })
+        .collect()
+}
+
+fn recorded_path(uri: &http::Uri) -> String {
+    uri.path_and_query()
+        .map(|path_and_query| path_and_query.as_str().to_owned())
+        .unwrap_or_else(|| uri.path().to_owned())
+}
+
+impl Cassette {
+    /// Loads the cassette from the file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| ConfigError::ReadCassetteError(path.to_owned(), err))?;
+        serde_json::from_str(&content)
+            .map_err(|err| ConfigError::ParseCassetteError(path.to_owned(), err))
+    }
+
+    /// Saves the cassette to the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+    }
+}
+
+/// A transport which records the interactions of the inner transport into the cassette file.
+/// The file is saved on each interaction.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    transport: Arc<dyn Transport>,
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+}
+
+impl RecordingTransport {
+    /// Creates a new [`RecordingTransport`] recording into the cassette file.
+    pub fn new(transport: impl Transport + 'static, path: impl Into<PathBuf>) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            path: path.into(),
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// Returns the recorded cassette.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let recorded_request = RecordedRequest {
+                method: request.method().to_string(),
+                path: recorded_path(request.uri()),
+                headers: recorded_headers(request.headers()),
+                body: String::from_utf8_lossy(request.body()).into_owned(),
+            };
+            let response = self.transport.send(request).await?;
+            let recorded_response = RecordedResponse {
+                status: response.status().as_u16(),
+                headers: recorded_headers(response.headers()),
+                body: String::from_utf8_lossy(response.body()).into_owned(),
+            };
+            let mut cassette = self.cassette.lock().unwrap();
+            cassette.interactions.push(Interaction {
+                request: recorded_request,
+                response: recorded_response,
+            });
+            cassette.save(&self.path).map_err(TransportError::other)?;
+            Ok(response)
+        })
+    }
+}
+
+/// A transport which replays the interactions of the cassette.
+///
+/// Each request is served by the first unused interaction of the same method, path and body,
+/// so the same requests are replayed in the recorded order.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl ReplayTransport {
+    /// Creates a new [`ReplayTransport`] from the cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    /// Creates a new [`ReplayTransport`] from the cassette file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Cassette::load(path).map(Self::new)
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+        let method = request.method().as_str();
+        let path = recorded_path(request.uri());
+        let body = String::from_utf8_lossy(request.body());
+        let interaction_opt = self
+            .interactions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|interaction| {
+                interaction.as_ref().is_some_and(|interaction| {
+                    interaction.request.method == method
+                        && interaction.request.path == path
+                        && interaction.request.body == body
+                })
+            })
+            .and_then(Option::take);
+        let result = match interaction_opt {
+            Some(interaction) => {
+                let mut builder = http::Response::builder().status(interaction.response.status);
+                for (name, value) in &interaction.response.headers {
+                    builder = builder.header(name, value);
+                }
+                builder
+                    .body(interaction.response.body.into_bytes())
+                    .map_err(TransportError::other)
+            }
+            None => Err(TransportError::other(format!(
+                "no recorded interaction for {} {}",
+                method, path
+            ))),
+        };
+        Box::pin(std::future::ready(result))
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+    use crate::cassette::*;
+    use crate::error::Error;
+    use crate::organization::Organization;
+    use crate::tests::*;
+
+    #[async_std::test]
+    async fn record_and_replay() {
+        let path = std::env::temp_dir().join(format!(
+            "mackerel-client-rs-test-cassette-{}.json",
+            std::process::id()
+        ));
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/org",
+            response = json!({ "name": "ExampleOrganization" }),
+        };
+        let organization = Organization::builder().name("ExampleOrganization").build();
+        let client = crate::Client::builder()
+            .api_key("<Mackerel-API-KEY>")
+            .api_base(server.url_str("/"))
+            .record_cassette(&path)
+            .build();
+        assert_eq!(client.get_organization().await, Ok(organization.clone()));
+        assert_eq!(client.get_organization().await, Ok(organization.clone()));
+
+        let cassette = Cassette::load(&path).unwrap();
+        assert_eq!(cassette.interactions.len(), 2);
+        assert_eq!(
+            cassette.interactions[0].request,
+            RecordedRequest {
+                method: "GET".to_owned(),
+                path: "/api/v0/org".to_owned(),
+                headers: BTreeMap::from([
+                    ("accept".to_owned(), "application/json".to_owned()),
+                    ("content-type".to_owned(), "application/json".to_owned()),
+                    (
+                        "user-agent".to_owned(),
+                        format!("mackerel-client-rs/{}", env!("CARGO_PKG_VERSION")),
+                    ),
+                    ("x-api-key".to_owned(), "<scrubbed>".to_owned()),
+                ]),
+                body: "".to_owned(),
+            },
+        );
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("<Mackerel-API-KEY>"));
+
+        let client = crate::Client::builder()
+            .api_key("<Mackerel-API-KEY>")
+            .api_base("https://mackerel.example.com")
+            .replay_cassette(&path)
+            .build();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(client.get_organization().await, Ok(organization.clone()));
+        assert_eq!(client.get_organization().await, Ok(organization));
+        assert!(matches!(
+            client.get_organization().await,
+            Err(Error::RequestError(err)) if err.to_string() == "no recorded interaction for GET /api/v0/org",
+        ));
+    }
+}
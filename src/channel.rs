@@ -4,7 +4,7 @@ use serde_derive::{Deserialize, Serialize};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::borrow::Borrow;
 use std::collections::HashMap;
-use strum::{Display, EnumString};
+use strum::{Display, EnumString, VariantNames};
 
 use crate::client::*;
 use crate::entity::{Entity, Id};
@@ -18,7 +18,8 @@ pub type Channel = Entity<ChannelValue>;
 pub type ChannelId = Id<ChannelValue>;
 
 /// A channel value
-#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, VariantNames)]
+#[strum(serialize_all = "kebab-case")]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum ChannelValue {
     #[serde(rename_all = "camelCase")]
@@ -52,6 +53,7 @@ pub enum ChannelValue {
         #[serde(default)]
         events: Vec<NotificationEvent>,
     },
+    #[serde(rename_all = "camelCase")]
     Line {
         name: String,
         #[serde(
@@ -60,7 +62,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Chatwork {
         name: String,
         #[serde(
@@ -69,7 +75,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Typetalk {
         name: String,
         #[serde(
@@ -78,7 +88,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Twilio {
         name: String,
         #[serde(
@@ -87,7 +101,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Pagerduty {
         name: String,
         #[serde(
@@ -96,7 +114,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Opsgenie {
         name: String,
         #[serde(
@@ -105,7 +127,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     Yammer {
         name: String,
         #[serde(
@@ -114,7 +140,11 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    #[serde(rename_all = "camelCase")]
     MicrosoftTeams {
         name: String,
         #[serde(
@@ -123,6 +153,9 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Webhook {
@@ -138,6 +171,7 @@ pub enum ChannelValue {
         #[serde(default)]
         events: Vec<NotificationEvent>,
     },
+    #[serde(rename_all = "camelCase")]
     AmazonEventBridge {
         name: String,
         #[serde(
@@ -146,7 +180,17 @@ pub enum ChannelValue {
             skip_serializing_if = "Option::is_none"
         )]
         suspended_at: Option<DateTime<Utc>>,
+        /// The settings of the channel not modeled by this library, preserved as is.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    /// A channel of the type not supported by this library.
+    /// The JSON object is preserved, including the `type` field.
+    #[serde(untagged)]
+    Unknown(
+        #[serde(deserialize_with = "crate::entity::deserialize_unknown::<ChannelValue, _>")]
+        serde_json::Map<String, serde_json::Value>,
+    ),
 }
 
 impl ChannelValue {
@@ -165,6 +209,11 @@ impl ChannelValue {
             Self::MicrosoftTeams { ref name, .. } => name.clone(),
             Self::Webhook { ref name, .. } => name.clone(),
             Self::AmazonEventBridge { ref name, .. } => name.clone(),
+            Self::Unknown(ref fields) => fields
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
         }
     }
 
@@ -183,6 +232,10 @@ impl ChannelValue {
             Self::MicrosoftTeams { suspended_at, .. } => suspended_at,
             Self::Webhook { suspended_at, .. } => suspended_at,
             Self::AmazonEventBridge { suspended_at, .. } => suspended_at,
+            Self::Unknown(ref fields) => fields
+                .get("suspendedAt")
+                .and_then(serde_json::Value::as_i64)
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
         }
     }
 }
@@ -269,6 +322,7 @@ mod tests {
             .value(ChannelValue::Line {
                 name: "Example Line Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -287,6 +341,7 @@ mod tests {
             .value(ChannelValue::Chatwork {
                 name: "Example Chatwork Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -305,6 +360,7 @@ mod tests {
             .value(ChannelValue::Typetalk {
                 name: "Example Typetalk Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -323,6 +379,7 @@ mod tests {
             .value(ChannelValue::Twilio {
                 name: "Example Twilio Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -341,6 +398,7 @@ mod tests {
             .value(ChannelValue::Pagerduty {
                 name: "Example Pagerduty Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -359,6 +417,7 @@ mod tests {
             .value(ChannelValue::Opsgenie {
                 name: "Example Opsgenie Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -377,6 +436,7 @@ mod tests {
             .value(ChannelValue::Yammer {
                 name: "Example Yammer Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -395,6 +455,7 @@ mod tests {
             .value(ChannelValue::MicrosoftTeams {
                 name: "Example MicrosoftTeams Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -441,6 +502,7 @@ mod tests {
             .value(ChannelValue::AmazonEventBridge {
                 name: "Example AmazonEventBridge Channel".to_string(),
                 suspended_at: None,
+                extra: Default::default(),
             })
             .build()
     }
@@ -453,6 +515,62 @@ mod tests {
         })
     }
 
+    fn chatwork_channel_with_settings_example() -> Channel {
+        Channel::builder()
+            .id("channel14")
+            .value(ChannelValue::Chatwork {
+                name: "Example Chatwork Channel".to_string(),
+                suspended_at: None,
+                extra: json!({
+                    "token": "chatwork-token",
+                    "roomId": "123456",
+                    "events": ["alert", "alertGroup"],
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            })
+            .build()
+    }
+
+    fn chatwork_channel_with_settings_json_example() -> serde_json::Value {
+        json!({
+            "id": "channel14",
+            "type": "chatwork",
+            "name": "Example Chatwork Channel",
+            "token": "chatwork-token",
+            "roomId": "123456",
+            "events": ["alert", "alertGroup"],
+        })
+    }
+
+    fn unknown_channel_example() -> Channel {
+        Channel::builder()
+            .id("channel13")
+            .value(ChannelValue::Unknown(
+                json!({
+                    "type": "new-channel-type",
+                    "name": "Example Unknown Channel",
+                    "suspendedAt": 1711360000,
+                    "setting": { "key": "value" },
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            ))
+            .build()
+    }
+
+    fn unknown_channel_json_example() -> serde_json::Value {
+        json!({
+            "id": "channel13",
+            "type": "new-channel-type",
+            "name": "Example Unknown Channel",
+            "suspendedAt": 1711360000,
+            "setting": { "key": "value" },
+        })
+    }
+
     #[rstest]
     #[case(email_channel_example(), email_channel_json_example())]
     #[case(slack_channel_example(), slack_channel_json_example())]
@@ -472,6 +590,11 @@ mod tests {
         amazon_event_bridge_channel_example(),
         amazon_event_bridge_json_example()
     )]
+    #[case(
+        chatwork_channel_with_settings_example(),
+        chatwork_channel_with_settings_json_example()
+    )]
+    #[case(unknown_channel_example(), unknown_channel_json_example())]
     fn test_channel_json(#[case] channel: Channel, #[case] json: serde_json::Value) {
         assert_eq!(serde_json::to_value(&channel).unwrap(), json);
         assert_eq!(channel, serde_json::from_value(json).unwrap());
@@ -493,10 +616,47 @@ mod tests {
         amazon_event_bridge_channel_example(),
         "Example AmazonEventBridge Channel"
     )]
+    #[case(unknown_channel_example(), "Example Unknown Channel")]
     fn test_channel_name(#[case] channel: Channel, #[case] name_str: &str) {
         assert_eq!(channel.name(), name_str);
     }
 
+    #[rstest]
+    #[case(email_channel_example(), Some(1711360000))]
+    #[case(line_channel_example(), None)]
+    #[case(unknown_channel_example(), Some(1711360000))]
+    fn test_channel_suspended_at(#[case] channel: Channel, #[case] suspended_at: Option<i64>) {
+        assert_eq!(
+            channel.suspended_at(),
+            suspended_at.and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+        );
+    }
+
+    #[test]
+    fn test_channel_without_settings() {
+        assert_eq!(
+            serde_json::from_value::<ChannelValue>(json!({
+                "type": "pagerduty",
+                "name": "Example Pagerduty Channel",
+            }))
+            .unwrap(),
+            ChannelValue::Pagerduty {
+                name: "Example Pagerduty Channel".to_string(),
+                suspended_at: None,
+                extra: Default::default(),
+            },
+        );
+    }
+
+    #[rstest]
+    #[case(json!({ "type": "email", "name": "Example Email Channel", "emails": "alice@example.com" }))]
+    #[case(json!({ "type": "slack", "name": "Example Slack Channel" }))]
+    #[case(json!({ "type": "amazon-event-bridge", "name": 1 }))]
+    #[case(json!({ "name": "Example Channel without type" }))]
+    fn test_channel_json_malformed(#[case] json: serde_json::Value) {
+        assert!(serde_json::from_value::<ChannelValue>(json).is_err());
+    }
+
     #[rstest]
     #[case(NotificationEvent::Alert, "alert")]
     #[case(NotificationEvent::AlertGroup, "alertGroup")]
@@ -521,54 +681,59 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the channels.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/channels#get>.
-    pub async fn list_channels(&self) -> Result<Vec<Channel>> {
-        self.request(
-            Method::GET,
-            "/api/v0/channels",
-            query_params![],
-            request_body![],
-            response_body! { channels: Vec<Channel> },
-        )
-        .await
-    }
-
-    /// Creates a new channel.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/channels#create>.
-    pub async fn create_channel(
-        &self,
-        channel_value: impl Borrow<ChannelValue>,
-    ) -> Result<Channel> {
-        self.request(
-            Method::POST,
-            "/api/v0/channels",
-            query_params![],
-            request_body!(channel_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Deletes a channel.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/channels#delete>.
-    pub async fn delete_channel(&self, channel_id: impl Into<ChannelId>) -> Result<Channel> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/channels/{}", channel_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Fetches all the channels.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/channels#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_channels(&self) -> Result<Vec<Channel>> {
+            self.request(
+                Method::GET,
+                "/api/v0/channels",
+                query_params![],
+                request_body![],
+                response_body! { channels: Vec<Channel> },
+            )
+            .await
+        }
+
+        /// Creates a new channel.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/channels#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_channel(
+            &self,
+            channel_value: impl Borrow<ChannelValue>,
+        ) -> Result<Channel> {
+            self.request(
+                Method::POST,
+                "/api/v0/channels",
+                query_params![],
+                request_body!(channel_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Deletes a channel.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/channels#delete>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(channel_id)))]
+        pub async fn delete_channel(&self, channel_id: impl Into<ChannelId>) -> Result<Channel> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/channels/{}", channel_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -641,6 +806,45 @@ mod client_tests {
         );
     }
 
+    #[async_std::test]
+    async fn create_chatwork_channel() {
+        let server = test_server! {
+            method = POST,
+            path = "/api/v0/channels",
+            request = json!({
+                "type": "chatwork",
+                "name": "Example Chatwork Channel",
+                "token": "chatwork-token",
+                "roomId": "123456",
+                "events": ["alert"],
+            }),
+            response = json!({
+                "id": "channel1",
+                "type": "chatwork",
+                "name": "Example Chatwork Channel",
+                "token": "chatwork-token",
+                "roomId": "123456",
+                "events": ["alert"],
+            }),
+        };
+        let channel_value = ChannelValue::Chatwork {
+            name: "Example Chatwork Channel".to_string(),
+            suspended_at: None,
+            extra: json!({
+                "token": "chatwork-token",
+                "roomId": "123456",
+                "events": ["alert"],
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        };
+        assert_eq!(
+            test_client!(server).create_channel(&channel_value).await,
+            Ok(Channel::new("channel1", channel_value)),
+        );
+    }
+
     #[async_std::test]
     async fn delete_channel() {
         let server = test_server! {
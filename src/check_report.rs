@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use http::Method;
 use serde_derive::{Deserialize, Serialize};
+use strum::VariantNames;
 use typed_builder::TypedBuilder;
 
 use crate::alert::AlertStatus;
@@ -28,11 +29,19 @@ pub struct CheckReport {
     pub max_check_attempts: Option<u64>,
 }
 
-#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, VariantNames)]
+#[strum(serialize_all = "camelCase")]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum CheckSource {
     #[serde(rename_all = "camelCase")]
     Host { host_id: HostId },
+    /// A source of the type not supported by this library.
+    /// The JSON object is preserved, including the `type` field.
+    #[serde(untagged)]
+    Unknown(
+        #[serde(deserialize_with = "crate::entity::deserialize_unknown::<CheckSource, _>")]
+        serde_json::Map<String, serde_json::Value>,
+    ),
 }
 
 #[cfg(test)]
@@ -94,38 +103,77 @@ mod tests {
         })
     }
 
+    fn check_report_example3() -> CheckReport {
+        CheckReport::builder()
+            .name("ExampleCheckReport3")
+            .source(CheckSource::Unknown(
+                json!({ "type": "newSourceType", "key": "value" })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ))
+            .status(AlertStatus::Ok)
+            .occurred_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+            .build()
+    }
+
+    fn json_example3() -> serde_json::Value {
+        json!({
+            "name": "ExampleCheckReport3",
+            "message": "",
+            "source": {
+                "type": "newSourceType",
+                "key": "value",
+            },
+            "status": "OK",
+            "occurredAt": 1700000000,
+        })
+    }
+
     #[rstest]
     #[case(check_report_example1(), json_example1())]
     #[case(check_report_example2(), json_example2())]
+    #[case(check_report_example3(), json_example3())]
     fn test_check_report_json(#[case] check_report: CheckReport, #[case] json: serde_json::Value) {
         assert_eq!(serde_json::to_value(&check_report).unwrap(), json);
         assert_eq!(check_report, serde_json::from_value(json).unwrap());
     }
+
+    #[rstest]
+    #[case(json!({ "type": "host" }))]
+    #[case(json!({ "type": "host", "hostId": 1 }))]
+    #[case(json!({ "hostId": "host0" }))]
+    fn test_check_source_json_malformed(#[case] json: serde_json::Value) {
+        assert!(serde_json::from_value::<CheckSource>(json).is_err());
+    }
 }
 
-impl Client {
-    /// Creates a new check report.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/check-monitoring#post>.
-    pub async fn create_check_report(
-        &self,
-        check_reports: impl IntoIterator<Item = CheckReport>,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/monitoring/checks/report",
-            query_params![],
-            request_body! {
-                reports: Vec<CheckReport> = check_reports
-                    .into_iter().collect::<Vec<_>>(),
-            },
-            response_body!(),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Creates a new check report.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/check-monitoring#post>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_check_report(
+            &self,
+            check_reports: impl IntoIterator<Item = CheckReport>,
+        ) -> Result<()> {
+            self.request(
+                Method::POST,
+                "/api/v0/monitoring/checks/report",
+                query_params![],
+                request_body! {
+                    reports: Vec<CheckReport> = check_reports
+                        .into_iter().collect::<Vec<_>>(),
+                },
+                response_body!(),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use crate::check_report::*;
     use crate::tests::*;
@@ -1,71 +1,371 @@
 //! Mackerel API client
+use base64::prelude::*;
 use http::{header::*, Method};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
-use typed_builder::TypedBuilder;
 use url::Url;
 
+use crate::cassette::{CassetteMode, RecordingTransport, ReplayTransport};
+use crate::dry_run::{DryRun, DryRunMode, DryRunRequest};
 use crate::error::*;
+use crate::middleware::{Middleware, Next};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::transport::Transport;
 
 /// An API client for Mackerel.
-#[derive(Debug, TypedBuilder)]
+#[derive(Debug)]
 pub struct Client {
-    #[builder(
-        default = "https://api.mackerelio.com".try_into().unwrap(),
-        setter(transform = |s: impl AsRef<str>| Url::parse(s.as_ref())
-            .unwrap_or_else(|err| panic!("invalid api_base ({:?}): {}", s.as_ref(), err))),
-    )]
     api_base: Url,
-
-    #[allow(dead_code)]
-    #[builder(
-        setter(transform = |s: impl AsRef<str>| HeaderValue::from_str(s.as_ref())
-            .map(|mut header_value| { header_value.set_sensitive(true); header_value })
-            .unwrap_or_else(|err| panic!("invalid api_key: {}", err))),
-    )]
     api_key: HeaderValue,
-
-    #[allow(dead_code)]
-    #[builder(
-        default = format!("mackerel-client-rs/{}", env!("CARGO_PKG_VERSION")).try_into().unwrap(),
-        setter(transform = |s: impl AsRef<str>| HeaderValue::from_str(s.as_ref())
-            .unwrap_or_else(|err| panic!("invalid user_agent ({:?}): {}", s.as_ref(), err))),
-    )]
+    authorization: Option<HeaderValue>,
     user_agent: HeaderValue,
-
     #[allow(dead_code)]
-    #[builder(
-        default = ::std::time::Duration::from_secs(30),
-        setter(transform = |d: impl Into<::std::time::Duration>| d.into()),
-    )]
     timeout: Duration,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    transport: Arc<dyn Transport>,
+    dry_run: Option<DryRun>,
+}
+
+/// A builder of [`Client`], created by [`Client::builder()`].
+///
+/// The API key is required to build the client; [`ClientBuilder::build()`] and
+/// [`ClientBuilder::try_build()`] are available after [`ClientBuilder::api_key()`].
+/// ```rust,compile_fail
+/// use mackerel_client::Client;
+///
+/// let client = Client::builder()
+///     .api_base("https://api.mackerelio.com")
+///     .build();
+/// ```
+///
+/// The settings are validated on [`ClientBuilder::try_build()`],
+/// which returns [`ConfigError`] instead of panicking.
+#[derive(Clone, Debug)]
+pub struct ClientBuilder<K = NoApiKey> {
+    api_base: Option<String>,
+    api_key: K,
+    user_agent: Option<String>,
+    timeout: Duration,
+    proxy: Option<String>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    write_rate_limit: Option<RateLimit>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    transport: Option<Arc<dyn Transport>>,
+    cassette: Option<(CassetteMode, PathBuf)>,
+    dry_run_mode: Option<DryRunMode>,
+}
+
+/// The state of [`ClientBuilder`] before the API key is set.
+#[derive(Clone, Copy, Debug)]
+pub struct NoApiKey;
+
+/// The state of [`ClientBuilder`] after the API key is set.
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ApiKey").field(&"<redacted>").finish()
+    }
+}
+
+impl ClientBuilder {
+    fn new() -> Self {
+        Self {
+            api_base: None,
+            api_key: NoApiKey,
+            user_agent: None,
+            timeout: Duration::from_secs(30),
+            proxy: None,
+            retry_policy: RetryPolicy::none(),
+            rate_limit: None,
+            write_rate_limit: None,
+            middlewares: Vec::new(),
+            transport: None,
+            cassette: None,
+            dry_run_mode: None,
+        }
+    }
+}
+
+impl<K> ClientBuilder<K> {
+    /// Sets the API base (default: `https://api.mackerelio.com`).
+    pub fn api_base(mut self, api_base: impl AsRef<str>) -> Self {
+        self.api_base = Some(api_base.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the API key.
+    pub fn api_key(self, api_key: impl AsRef<str>) -> ClientBuilder<ApiKey> {
+        ClientBuilder {
+            api_base: self.api_base,
+            api_key: ApiKey(api_key.as_ref().to_owned()),
+            user_agent: self.user_agent,
+            timeout: self.timeout,
+            proxy: self.proxy,
+            retry_policy: self.retry_policy,
+            rate_limit: self.rate_limit,
+            write_rate_limit: self.write_rate_limit,
+            middlewares: self.middlewares,
+            transport: self.transport,
+            cassette: self.cassette,
+            dry_run_mode: self.dry_run_mode,
+        }
+    }
+
+    /// Sets the user agent (default: `mackerel-client-rs/<version>`).
+    pub fn user_agent(mut self, user_agent: impl AsRef<str>) -> Self {
+        self.user_agent = Some(user_agent.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the timeout of each request (default: 30s).
+    /// This is ignored when a custom transport is configured.
+    pub fn timeout(mut self, timeout: impl Into<Duration>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Sets the proxy for all the requests.
+    /// This is ignored when a custom transport is configured.
+    pub fn proxy(mut self, proxy: impl AsRef<str>) -> Self {
+        self.proxy = Some(proxy.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the retry policy (default: [`RetryPolicy::none()`]).
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the rate limit of the requests.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Sets the rate limit of the write (non-GET) requests.
+    pub fn write_rate_limit(mut self, write_rate_limit: RateLimit) -> Self {
+        self.write_rate_limit = Some(write_rate_limit);
+        self
+    }
+
+    /// Appends a [`Middleware`] to the middleware chain.
+    /// The middlewares are called in the order of addition.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sets the [`reqwest::Client`] to send the requests.
+    #[cfg(feature = "reqwest")]
+    pub fn reqwest_client(self, client: reqwest::Client) -> Self {
+        self.transport(crate::transport::ReqwestTransport::new(client))
+    }
+
+    /// Sets the [`Transport`] to send the requests (default: [`reqwest`] based transport).
+    ///
+    /// The userinfo of the API base is not passed to the transport in the URI,
+    /// but is sent as the basic authentication in the `Authorization` header.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Records the interactions of the transport into the cassette file.
+    /// The `x-api-key` header is scrubbed from the recorded requests.
+    /// See [`crate::cassette`] for details.
+    pub fn record_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some((CassetteMode::Record, path.into()));
+        self
+    }
+
+    /// Replays the interactions of the cassette file instead of sending the requests.
+    /// See [`crate::cassette`] for details.
+    pub fn replay_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some((CassetteMode::Replay, path.into()));
+        self
+    }
+
+    /// Enables the dry-run mode, where the write (non-GET) requests are not sent.
+    /// See [`crate::dry_run`] for details.
+    pub fn dry_run(mut self, dry_run_mode: DryRunMode) -> Self {
+        self.dry_run_mode = Some(dry_run_mode);
+        self
+    }
+}
+
+impl ClientBuilder<ApiKey> {
+    /// Builds the API client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is invalid. Use [`ClientBuilder::try_build()`] to handle the error.
+    pub fn build(self) -> Client {
+        self.try_build().unwrap_or_else(|err| panic!("{}", err))
+    }
 
-    #[builder(
-        default = ::reqwest::Client::builder()
-            .default_headers(HeaderMap::from_iter([
-                (HeaderName::from_static("x-api-key"), api_key.clone()),
-                (USER_AGENT, user_agent.clone()),
-                (CONTENT_TYPE, HeaderValue::from_static("application/json")),
-                (ACCEPT, HeaderValue::from_static("application/json")),
-            ]))
-            .redirect(::reqwest::redirect::Policy::none())
+    /// Builds the API client, or returns [`ConfigError`] if the configuration is invalid.
+    /// ```rust
+    /// use mackerel_client::error::ConfigError;
+    /// use mackerel_client::Client;
+    ///
+    /// let result = Client::builder()
+    ///     .api_key("<Mackerel-API-KEY>")
+    ///     .api_base("invalid url")
+    ///     .try_build();
+    /// assert!(matches!(result, Err(ConfigError::InvalidApiBase(..))));
+    /// ```
+    pub fn try_build(self) -> std::result::Result<Client, ConfigError> {
+        let mut api_base = match self.api_base {
+            Some(api_base) => {
+                Url::parse(&api_base).map_err(|err| ConfigError::InvalidApiBase(api_base, err))?
+            }
+            None => "https://api.mackerelio.com".try_into().unwrap(),
+        };
+        // The userinfo of the API base is sent as the basic authentication,
+        // so that the transports do not receive the credentials in the URI.
+        let authorization = if !api_base.username().is_empty() || api_base.password().is_some() {
+            let credentials = format!(
+                "{}:{}",
+                api_base.username(),
+                api_base.password().unwrap_or_default(),
+            );
+            let mut header_value =
+                HeaderValue::try_from(format!("Basic {}", BASE64_STANDARD.encode(credentials)))
+                    .unwrap();
+            header_value.set_sensitive(true);
+            let _ = api_base.set_username("");
+            let _ = api_base.set_password(None);
+            Some(header_value)
+        } else {
+            None
+        };
+        let api_key = HeaderValue::from_str(&self.api_key.0)
+            .map(|mut header_value| {
+                header_value.set_sensitive(true);
+                header_value
+            })
+            .map_err(ConfigError::InvalidApiKey)?;
+        let user_agent = match self.user_agent {
+            Some(user_agent) => HeaderValue::from_str(&user_agent)
+                .map_err(|err| ConfigError::InvalidUserAgent(user_agent, err))?,
+            None => format!("mackerel-client-rs/{}", env!("CARGO_PKG_VERSION"))
+                .try_into()
+                .unwrap(),
+        };
+        let transport: Arc<dyn Transport> = match (self.cassette, self.transport) {
+            (Some((CassetteMode::Replay, path)), _) => Arc::new(ReplayTransport::load(path)?),
+            (cassette_opt, transport_opt) => {
+                let transport = match transport_opt {
+                    Some(transport) => transport,
+                    None => Self::default_transport(self.proxy, self.timeout)?,
+                };
+                match cassette_opt {
+                    Some((CassetteMode::Record, path)) => {
+                        Arc::new(RecordingTransport::new(transport, path))
+                    }
+                    _ => transport,
+                }
+            }
+        };
+        Ok(Client {
+            api_base,
+            api_key,
+            authorization,
+            user_agent,
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            rate_limiter: RateLimiter::new(self.rate_limit, self.write_rate_limit),
+            middlewares: self.middlewares,
+            transport,
+            dry_run: self.dry_run_mode.map(DryRun::new),
+        })
+    }
+
+    /// Builds the blocking API client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is invalid. Use [`ClientBuilder::try_build_blocking()`] to handle the error.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> crate::blocking::Client {
+        self.try_build_blocking()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Builds the blocking API client, or returns [`ConfigError`] if the configuration is invalid.
+    /// The default transport is based on [`reqwest::blocking::Client`].
+    #[cfg(feature = "blocking")]
+    pub fn try_build_blocking(
+        mut self,
+    ) -> std::result::Result<crate::blocking::Client, ConfigError> {
+        if self.transport.is_none() {
+            let mut client_builder = reqwest::blocking::Client::builder();
+            if let Some(proxy) = self.proxy.take() {
+                client_builder = client_builder.proxy(
+                    reqwest::Proxy::all(&proxy)
+                        .map_err(|err| ConfigError::InvalidProxy(proxy, err))?,
+                );
+            }
+            let client = client_builder
+                .redirect(reqwest::redirect::Policy::none())
+                .timeout(self.timeout)
+                .build()
+                .map_err(ConfigError::HttpClientError)?;
+            self = self.transport(crate::blocking::BlockingReqwestTransport::new(client));
+        }
+        Ok(crate::blocking::Client {
+            client: self.try_build()?,
+        })
+    }
+
+    #[cfg(feature = "reqwest")]
+    fn default_transport(
+        proxy_opt: Option<String>,
+        timeout: Duration,
+    ) -> std::result::Result<Arc<dyn Transport>, ConfigError> {
+        let mut client_builder = reqwest::Client::builder();
+        if let Some(proxy) = proxy_opt {
+            client_builder = client_builder.proxy(
+                reqwest::Proxy::all(&proxy).map_err(|err| ConfigError::InvalidProxy(proxy, err))?,
+            );
+        }
+        let client = client_builder
+            .redirect(reqwest::redirect::Policy::none())
             .timeout(timeout)
             .build()
-            .unwrap_or_else(|err| panic!("{}", err)),
-        setter(skip),
-    )]
-    client: reqwest::Client,
+            .map_err(ConfigError::HttpClientError)?;
+        Ok(Arc::new(crate::transport::ReqwestTransport::new(client)))
+    }
+
+    #[cfg(not(feature = "reqwest"))]
+    fn default_transport(
+        _proxy_opt: Option<String>,
+        _timeout: Duration,
+    ) -> std::result::Result<Arc<dyn Transport>, ConfigError> {
+        Err(ConfigError::MissingTransport)
+    }
 }
 
 impl Client {
     /// Creates a new API client from API key.
-    /// ```rust
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
     /// use mackerel_client::Client;
     ///
     /// let client = Client::new("<Mackerel-API-KEY>");
     /// ```
     ///
     /// If you want to configure the API base, use [`Client::builder()`].
-    /// ```rust
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
     /// use mackerel_client::Client;
     ///
     /// let client = Client::builder()
@@ -75,7 +375,8 @@ impl Client {
     /// ```
     ///
     /// You can configure user agent and timeout (default: 30s).
-    /// ```rust
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
     /// use mackerel_client::Client;
     /// use std::time::Duration;
     ///
@@ -85,10 +386,70 @@ impl Client {
     ///     .timeout(Duration::from_secs(60))
     ///     .build();
     /// ```
+    ///
+    /// Requests are not retried by default. Configure [`RetryPolicy`] to retry on transient errors.
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+    /// use mackerel_client::retry::RetryPolicy;
+    /// use mackerel_client::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("<Mackerel-API-KEY>")
+    ///     .retry_policy(RetryPolicy::default())
+    ///     .build();
+    /// ```
+    ///
+    /// Configure [`RateLimit`] to throttle the requests on the client side.
+    /// The write requests share the same bucket unless `write_rate_limit` is configured.
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+    /// use mackerel_client::rate_limit::RateLimit;
+    /// use mackerel_client::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("<Mackerel-API-KEY>")
+    ///     .rate_limit(RateLimit::per_minute(300))
+    ///     .write_rate_limit(RateLimit::per_minute(60))
+    ///     .build();
+    /// ```
     pub fn new(api_key: impl AsRef<str>) -> Client {
         Self::builder().api_key(api_key).build()
     }
 
+    /// Creates a new API client from API key, or returns [`ConfigError`] if the API key is invalid.
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+    /// use mackerel_client::Client;
+    ///
+    /// let client = Client::try_new("<Mackerel-API-KEY>").unwrap();
+    /// assert!(Client::try_new("invalid\nAPI key").is_err());
+    /// ```
+    pub fn try_new(api_key: impl AsRef<str>) -> std::result::Result<Client, ConfigError> {
+        Self::builder().api_key(api_key).try_build()
+    }
+
+    /// Creates a new [`ClientBuilder`] to configure the API client.
+    #[cfg_attr(feature = "reqwest", doc = "```rust")]
+    #[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+    /// use mackerel_client::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("<Mackerel-API-KEY>")
+    ///     .reqwest_client(reqwest::Client::new())
+    ///     .build();
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Returns the write requests recorded in the dry-run mode.
+    pub fn dry_run_requests(&self) -> Vec<DryRunRequest> {
+        self.dry_run
+            .as_ref()
+            .map(DryRun::requests)
+            .unwrap_or_default()
+    }
+
     pub(crate) async fn request<R, S>(
         &self,
         method: Method,
@@ -114,52 +475,135 @@ impl Client {
         let request_body_bytes = request_body_opt
             .map(|b| serde_json::to_vec(&b).unwrap())
             .unwrap_or_default();
-        let response = {
-            let request = self
-                .client
-                .request(method, url.clone())
-                .body(request_body_bytes);
-            if url.username() != "" {
-                request.basic_auth(url.username(), url.password())
-            } else {
-                request
-            }
+        if let Some(dry_run) = self.dry_run.as_ref().filter(|_| method != Method::GET) {
+            return dry_run
+                .handle(&method, &url, &request_body_bytes)
+                .into_iter()
+                .find_map(|response_body| serde_json::from_value::<R>(response_body).ok())
+                .map(converter)
+                .ok_or_else(|| Error::DryRun {
+                    method,
+                    path: url.path().to_owned(),
+                });
         }
-        .send()
-        .await?;
+        let send = self.send(&method, &url, request_body_bytes);
+        #[cfg(feature = "tracing")]
+        let send = tracing::Instrument::instrument(
+            send,
+            tracing::info_span!(
+                "request",
+                http.method = %method,
+                http.path = url.path(),
+                http.status_code = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            ),
+        );
+        let response = send.await?;
         if !response.status().is_success() {
-            return Err(self.api_error(response).await);
+            return Err(Self::api_error(method, url.path(), response));
         }
-        Ok(converter(response.json::<R>().await?))
+        let body = response.into_body();
+        let response_body =
+            serde_json::from_slice::<R>(&body).map_err(|err| Error::DecodeError {
+                method,
+                path: url.path().to_owned(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+                source: err,
+            })?;
+        Ok(converter(response_body))
     }
 
-    async fn api_error(&self, response: reqwest::Response) -> Error {
-        let status = response.status();
-        let body = match response.text().await {
-            Ok(text) => text,
-            Err(err) => return err.into(),
+    async fn send(
+        &self,
+        method: &Method,
+        url: &Url,
+        request_body_bytes: Vec<u8>,
+    ) -> Result<http::Response<Vec<u8>>> {
+        #[cfg(feature = "tracing")]
+        let start = std::time::Instant::now();
+        let mut attempt = 0;
+        let result = loop {
+            attempt += 1;
+            self.rate_limiter.acquire(method).await;
+            let mut request = http::Request::builder()
+                .method(method.clone())
+                .uri(url.as_str())
+                .header(HeaderName::from_static("x-api-key"), self.api_key.clone())
+                .header(USER_AGENT, self.user_agent.clone())
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .header(ACCEPT, HeaderValue::from_static("application/json"));
+            if let Some(authorization) = &self.authorization {
+                request = request.header(AUTHORIZATION, authorization.clone());
+            }
+            let request = request.body(request_body_bytes.clone()).unwrap();
+            let result = Next::new(&self.middlewares, &*self.transport)
+                .run(request)
+                .await;
+            let can_retry = self.retry_policy.can_retry(method, attempt);
+            let delay = match result {
+                Ok(response)
+                    if can_retry && self.retry_policy.is_retryable_status(response.status()) =>
+                {
+                    self.retry_policy.delay(attempt, Some(response.headers()))
+                }
+                Err(err) if can_retry && self.retry_policy.is_retryable_error(&err) => {
+                    self.retry_policy.delay(attempt, None)
+                }
+                result => break result,
+            };
+            futures_timer::Delay::new(delay).await;
         };
-        Error::ApiError(
-            status,
-            serde_json::from_str::<serde_json::Value>(&body)
-                .ok()
-                .and_then(|value: serde_json::Value| {
-                    value
-                        .get("error")
-                        .map(|err| err.get("message").unwrap_or(err))
-                        .and_then(serde_json::Value::as_str)
-                        .map(str::to_owned)
-                })
-                .unwrap_or(body),
-        )
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            if let Ok(response) = &result {
+                span.record("http.status_code", response.status().as_u16());
+            }
+            span.record("retry_count", attempt - 1);
+            span.record("latency_ms", start.elapsed().as_millis() as u64);
+        }
+        Ok(result?)
+    }
+
+    fn api_error(method: Method, path: &str, response: http::Response<Vec<u8>>) -> Error {
+        let status = response.status();
+        let retry_after = crate::retry::retry_after(response.headers());
+        let body = String::from_utf8_lossy(response.body()).into_owned();
+        ApiErrorDetail::new(method, path, status, retry_after, body).into_error()
     }
 }
 
-macro_rules! format_url {
-    ( $format:expr, $( $args:expr ),+ $(,)? ) => {
-        format!($format, $( $args.into() ),+)
+/// Records the value in the field of the span of the API method.
+/// The value is evaluated only when the `tracing` feature is enabled.
+macro_rules! record_field {
+    ( $field:ident, $value:expr $(,)? ) => {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record(stringify!($field), tracing::field::display(&$value));
     };
 }
+pub(crate) use record_field;
+
+/// Joins the ids with commas to record them in a field.
+#[cfg(feature = "tracing")]
+pub(crate) fn join_ids(ids: &[impl std::fmt::Display]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Formats the path with the arguments converted into the ids,
+/// and records the ids in the fields of the span of the API method.
+macro_rules! format_url {
+    ( $format:expr, $( $args:ident ),+ $(,)? ) => {{
+        $(
+            let $args = $args.into();
+            $crate::client::record_field!($args, $args);
+        )+
+        format!($format, $( $args ),+)
+    }};
+}
 pub(crate) use format_url;
 
 macro_rules! query_params {
@@ -205,9 +649,45 @@ macro_rules! response_body {
 }
 pub(crate) use response_body;
 
-#[cfg(test)]
+/// Defines the async methods of [`crate::Client`], and the blocking versions of them.
+macro_rules! client_methods {
+    (
+        impl Client {
+            $(
+                $( #[$attr:meta] )*
+                pub async fn $name:ident(&$self:ident $(, $arg:ident: $type:ty )* $(,)? )
+                    -> $result:ty $body:block
+            )+
+        }
+    ) => {
+        impl $crate::Client {
+            $(
+                $( #[$attr] )*
+                pub async fn $name(&$self $(, $arg: $type )*) -> $result $body
+            )+
+        }
+
+        #[cfg(feature = "blocking")]
+        #[allow(clippy::result_large_err)]
+        impl $crate::blocking::Client {
+            $(
+                #[doc = concat!(
+                    "Blocking version of [`Client::", stringify!($name), "()`]",
+                    "(crate::Client::", stringify!($name), ")."
+                )]
+                pub fn $name(&self $(, $arg: $type )*) -> $result {
+                    self.block_on(self.client.$name($( $arg ),*))
+                }
+            )+
+        }
+    };
+}
+pub(crate) use client_methods;
+
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use http::StatusCode;
+    use serde_json::json;
 
     use crate::client::*;
     use crate::tests::*;
@@ -268,6 +748,23 @@ mod client_tests {
         }
     }
 
+    fn api_error_detail(
+        method: Method,
+        status: StatusCode,
+        message: &str,
+        body: impl std::fmt::Display,
+    ) -> ApiErrorDetail {
+        ApiErrorDetail {
+            method,
+            path: "/api/v0/test".to_owned(),
+            status,
+            message: message.to_owned(),
+            field_errors: Default::default(),
+            retry_after: None,
+            body: body.to_string(),
+        }
+    }
+
     #[async_std::test]
     async fn error() {
         {
@@ -281,10 +778,12 @@ mod client_tests {
             };
             assert_eq!(
                 test_client!(server).get().await,
-                Err(Error::ApiError(
+                Err(Error::ValidationError(api_error_detail(
+                    Method::GET,
                     StatusCode::BAD_REQUEST,
-                    "This is an error message.".to_owned()
-                )),
+                    "This is an error message.",
+                    json!({ "error": "This is an error message." }),
+                ))),
             );
         }
         {
@@ -298,13 +797,17 @@ mod client_tests {
                     },
                 }),
             };
+            let result = test_client!(server).get().await;
             assert_eq!(
-                test_client!(server).get().await,
-                Err(Error::ApiError(
+                result,
+                Err(Error::NotFound(api_error_detail(
+                    Method::GET,
                     StatusCode::NOT_FOUND,
-                    "This is an error message.".to_owned()
-                )),
+                    "This is an error message.",
+                    json!({ "error": { "message": "This is an error message." } }),
+                ))),
             );
+            assert!(result.unwrap_err().is_not_found());
         }
         {
             let server = test_server! {
@@ -313,13 +816,252 @@ mod client_tests {
                 status_code = 500,
                 response = "This is an error message.",
             };
+            let result = test_client!(server).get().await;
             assert_eq!(
-                test_client!(server).get().await,
-                Err(Error::ApiError(
+                result,
+                Err(Error::ServerError(api_error_detail(
+                    Method::GET,
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    r#""This is an error message.""#.to_owned()
-                )),
+                    r#""This is an error message.""#,
+                    json!("This is an error message."),
+                ))),
+            );
+            assert!(result.unwrap_err().is_retryable());
+        }
+        {
+            let server = test_server! {
+                method = POST,
+                path = "/api/v0/test",
+                query_params = "param1=value1&param2=value2&param3=value3",
+                status_code = 409,
+                request = json!({ "message": "request body" }),
+                response = json!({ "error": { "message": "Conflict." } }),
+            };
+            assert_eq!(
+                test_client!(server).post().await,
+                Err(Error::Conflict(api_error_detail(
+                    Method::POST,
+                    StatusCode::CONFLICT,
+                    "Conflict.",
+                    json!({ "error": { "message": "Conflict." } }),
+                ))),
+            );
+        }
+        {
+            let server = test_server! {
+                method = GET,
+                path = "/api/v0/test",
+                status_code = 200,
+                response = json!({ "success": true }),
+            };
+            match test_client!(server).get().await {
+                Err(Error::DecodeError {
+                    method, path, body, ..
+                }) => {
+                    assert_eq!(method, Method::GET);
+                    assert_eq!(path, "/api/v0/test");
+                    assert_eq!(body, r#"{"success":true}"#);
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+    }
+
+    #[async_std::test]
+    async fn retry() {
+        use httptest::{cycle, matchers::*, responders::*, Expectation};
+        let retry_client = |server: &httptest::Server| {
+            Client::builder()
+                .api_key("")
+                .api_base(server.url_str("/"))
+                .retry_policy(
+                    RetryPolicy::builder()
+                        .base_delay(Duration::from_millis(1))
+                        .build(),
+                )
+                .build()
+        };
+        {
+            let server = TEST_SERVER_POOL.get_server();
+            server.expect(
+                Expectation::matching(request::method_path("GET", "/api/v0/test"))
+                    .times(3)
+                    .respond_with(cycle![
+                        status_code(503),
+                        status_code(429).append_header("Retry-After", "0"),
+                        status_code(200).body("true"),
+                    ]),
+            );
+            assert_eq!(retry_client(&server).get().await, Ok(true));
+        }
+        {
+            let server = TEST_SERVER_POOL.get_server();
+            server.expect(
+                Expectation::matching(request::method_path("GET", "/api/v0/test"))
+                    .times(3)
+                    .respond_with(status_code(502)),
+            );
+            assert_eq!(
+                retry_client(&server).get().await,
+                Err(Error::ServerError(api_error_detail(
+                    Method::GET,
+                    StatusCode::BAD_GATEWAY,
+                    "",
+                    "",
+                ))),
             );
         }
+        {
+            let server = TEST_SERVER_POOL.get_server();
+            server.expect(
+                Expectation::matching(request::method_path("POST", "/api/v0/test"))
+                    .times(1)
+                    .respond_with(status_code(503)),
+            );
+            assert_eq!(
+                retry_client(&server).post().await,
+                Err(Error::ServerError(api_error_detail(
+                    Method::POST,
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "",
+                    "",
+                ))),
+            );
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[async_std::test]
+    async fn tracing() {
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        type Spans = Arc<Mutex<Vec<(&'static Metadata<'static>, Vec<String>)>>>;
+
+        #[derive(Default)]
+        struct Subscriber {
+            spans: Spans,
+            stack: Mutex<Vec<Id>>,
+        }
+
+        struct Visitor<'a>(&'a mut Vec<String>);
+
+        impl Visit for Visitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl tracing::Subscriber for Subscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+                let mut spans = self.spans.lock().unwrap();
+                let mut fields = Vec::new();
+                attrs.record(&mut Visitor(&mut fields));
+                spans.push((attrs.metadata(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, id: &Id, values: &Record<'_>) {
+                let mut spans = self.spans.lock().unwrap();
+                values.record(&mut Visitor(&mut spans[id.into_u64() as usize - 1].1));
+            }
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, id: &Id) {
+                self.stack.lock().unwrap().push(id.clone());
+            }
+            fn exit(&self, _: &Id) {
+                self.stack.lock().unwrap().pop();
+            }
+            fn current_span(&self) -> tracing_core::span::Current {
+                match self.stack.lock().unwrap().last() {
+                    Some(id) => tracing_core::span::Current::new(
+                        id.clone(),
+                        self.spans.lock().unwrap()[id.into_u64() as usize - 1].0,
+                    ),
+                    None => tracing_core::span::Current::none(),
+                }
+            }
+        }
+
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/hosts/host1",
+            status_code = 404,
+            response = json!({ "error": { "message": "Host not found." } }),
+        };
+        let subscriber = Subscriber::default();
+        let spans = subscriber.spans.clone();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        assert!(test_client!(server)
+            .get_host("host1")
+            .await
+            .is_err_and(|err| err.is_not_found()));
+        let spans = spans.lock().unwrap();
+        assert_eq!(
+            spans
+                .iter()
+                .map(|(metadata, _)| metadata.name())
+                .collect::<Vec<_>>(),
+            vec!["get_host", "request"],
+        );
+        assert_eq!(spans[0].1, ["host_id=host1"]);
+        let fields = &spans[1].1;
+        assert_eq!(
+            fields[..4],
+            [
+                "http.method=GET",
+                "http.path=\"/api/v0/hosts/host1\"",
+                "http.status_code=404",
+                "retry_count=0",
+            ],
+        );
+        assert!(fields[4].starts_with("latency_ms="));
+    }
+
+    #[test]
+    fn config_error() {
+        assert!(Client::try_new("<Mackerel-API-KEY>").is_ok());
+        assert!(matches!(
+            Client::try_new("invalid\nAPI key"),
+            Err(ConfigError::InvalidApiKey(_)),
+        ));
+        assert!(matches!(
+            Client::builder().api_key("").api_base("invalid url").try_build(),
+            Err(ConfigError::InvalidApiBase(api_base, _)) if api_base == "invalid url",
+        ));
+        #[cfg(feature = "reqwest")]
+        assert!(matches!(
+            Client::builder().api_key("").proxy("invalid proxy").try_build(),
+            Err(ConfigError::InvalidProxy(proxy, _)) if proxy == "invalid proxy",
+        ));
+        assert!(matches!(
+            Client::builder().api_key("").user_agent("invalid\nUA").try_build(),
+            Err(ConfigError::InvalidUserAgent(user_agent, _)) if user_agent == "invalid\nUA",
+        ));
+    }
+
+    #[test]
+    fn builder_debug() {
+        let builder = Client::builder()
+            .api_key("<Mackerel-API-KEY>")
+            .user_agent("custom-user-agent/0.0");
+        let debug = format!("{:?}", builder.clone());
+        assert!(debug.contains("custom-user-agent/0.0"));
+        assert!(!debug.contains("<Mackerel-API-KEY>"));
+        assert!(builder.try_build().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid api_base")]
+    fn config_error_panic() {
+        Client::builder()
+            .api_key("")
+            .api_base("invalid url")
+            .build();
     }
 }
@@ -0,0 +1,282 @@
+//! Configuration of the API client from the environment variables and the agent configuration
+use serde_derive::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::client::{ApiKey, Client, ClientBuilder};
+use crate::error::ConfigError;
+
+/// The environment variable of the API key, used by mackerel-agent and mkr.
+pub const APIKEY_ENV: &str = "MACKEREL_APIKEY";
+
+/// The environment variable of the API base, used by mackerel-agent and mkr.
+pub const APIBASE_ENV: &str = "MACKEREL_APIBASE";
+
+/// The default path of the configuration file of mackerel-agent.
+pub const DEFAULT_AGENT_CONFIG_PATH: &str = "/etc/mackerel-agent/mackerel-agent.conf";
+
+/// The source of a configuration value.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ConfigSource {
+    /// The value is read from the environment variable.
+    Env(&'static str),
+    /// The value is read from the configuration file of mackerel-agent.
+    AgentConfig(PathBuf),
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "environment variable {}", name),
+            Self::AgentConfig(path) => write!(f, "agent config {}", path.display()),
+        }
+    }
+}
+
+/// A configuration value with its source.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ConfigValue {
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// The settings of the API client loaded from the environment variables
+/// and the configuration file of mackerel-agent.
+/// The missing configuration file is regarded as empty,
+/// so the environment variables work on the machines without mackerel-agent.
+///
+/// ```rust,no_run
+/// use mackerel_client::config::{ClientConfig, DEFAULT_AGENT_CONFIG_PATH};
+///
+/// # fn main() -> Result<(), mackerel_client::error::ConfigError> {
+/// let config = ClientConfig::from_env()
+///     .or(ClientConfig::from_agent_config(DEFAULT_AGENT_CONFIG_PATH)?);
+/// if let Some(api_key) = &config.api_key {
+///     println!("api key is loaded from {}", api_key.source);
+/// }
+/// let client = config.builder()?.try_build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(PartialEq, Eq, Clone, Default)]
+pub struct ClientConfig {
+    pub api_key: Option<ConfigValue>,
+    pub api_base: Option<ConfigValue>,
+    pub proxy: Option<ConfigValue>,
+}
+
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field(
+                "api_key",
+                &self.api_key.as_ref().map(|api_key| ConfigValue {
+                    value: "<redacted>".to_owned(),
+                    source: api_key.source.clone(),
+                }),
+            )
+            .field("api_base", &self.api_base)
+            .field("proxy", &self.proxy)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct AgentConfig {
+    apikey: Option<String>,
+    apibase: Option<String>,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+}
+
+impl ClientConfig {
+    /// Loads the settings from `MACKEREL_APIKEY` and `MACKEREL_APIBASE`.
+    pub fn from_env() -> Self {
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
+
+    fn from_env_with(lookup: impl Fn(&'static str) -> Option<String>) -> Self {
+        let value = |name| {
+            lookup(name)
+                .filter(|value| !value.is_empty())
+                .map(|value| ConfigValue {
+                    value,
+                    source: ConfigSource::Env(name),
+                })
+        };
+        Self {
+            api_key: value(APIKEY_ENV),
+            api_base: value(APIBASE_ENV),
+            proxy: None,
+        }
+    }
+
+    /// Loads the settings (`apikey`, `apibase`, `https_proxy` and `http_proxy`)
+    /// from the configuration file of mackerel-agent.
+    /// Returns the empty settings if the file does not exist.
+    pub fn from_agent_config(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(ConfigError::ReadAgentConfigError(path.to_owned(), err)),
+        };
+        let agent_config = toml::from_str::<AgentConfig>(&content)
+            .map_err(|err| ConfigError::ParseAgentConfigError(path.to_owned(), err))?;
+        let value = |value_opt: Option<String>| {
+            value_opt
+                .filter(|value| !value.is_empty())
+                .map(|value| ConfigValue {
+                    value,
+                    source: ConfigSource::AgentConfig(path.to_owned()),
+                })
+        };
+        Ok(Self {
+            api_key: value(agent_config.apikey),
+            api_base: value(agent_config.apibase),
+            proxy: value(agent_config.https_proxy.or(agent_config.http_proxy)),
+        })
+    }
+
+    /// Fills the missing settings with the other configuration.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            api_key: self.api_key.or(other.api_key),
+            api_base: self.api_base.or(other.api_base),
+            proxy: self.proxy.or(other.proxy),
+        }
+    }
+
+    /// Creates a [`ClientBuilder`] with the settings,
+    /// or returns [`ConfigError::MissingApiKey`] if the API key is not configured.
+    pub fn builder(self) -> Result<ClientBuilder<ApiKey>, ConfigError> {
+        let api_key = self.api_key.ok_or(ConfigError::MissingApiKey)?;
+        let mut builder = Client::builder().api_key(api_key.value);
+        if let Some(api_base) = self.api_base {
+            builder = builder.api_base(api_base.value);
+        }
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy.value);
+        }
+        Ok(builder)
+    }
+}
+
+impl Client {
+    /// Creates a new API client from the environment variables `MACKEREL_APIKEY` and `MACKEREL_APIBASE`.
+    /// ```rust,no_run
+    /// use mackerel_client::Client;
+    ///
+    /// let client = Client::from_env().unwrap();
+    /// ```
+    ///
+    /// Use [`ClientConfig`] to load the configuration file of mackerel-agent.
+    pub fn from_env() -> Result<Client, ConfigError> {
+        ClientConfig::from_env().builder()?.try_build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_env() {
+        let env = HashMap::from([
+            (APIKEY_ENV, "env-api-key".to_owned()),
+            (APIBASE_ENV, "".to_owned()),
+        ]);
+        assert_eq!(
+            ClientConfig::from_env_with(|name| env.get(name).cloned()),
+            ClientConfig {
+                api_key: Some(ConfigValue {
+                    value: "env-api-key".to_owned(),
+                    source: ConfigSource::Env(APIKEY_ENV),
+                }),
+                api_base: None,
+                proxy: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_agent_config() {
+        let path = std::env::temp_dir().join(format!(
+            "mackerel-client-rs-test-{}.conf",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            r#"
+apikey = "agent-api-key"
+apibase = "https://mackerel.example.com"
+http_proxy = "http://proxy.example.com:8080"
+roles = ["service:role"]
+
+[plugin.metrics.foo]
+command = "foo"
+"#,
+        )
+        .unwrap();
+        let config = ClientConfig::from_agent_config(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let source = ConfigSource::AgentConfig(path.clone());
+        assert_eq!(
+            config,
+            ClientConfig {
+                api_key: Some(ConfigValue {
+                    value: "agent-api-key".to_owned(),
+                    source: source.clone(),
+                }),
+                api_base: Some(ConfigValue {
+                    value: "https://mackerel.example.com".to_owned(),
+                    source: source.clone(),
+                }),
+                proxy: Some(ConfigValue {
+                    value: "http://proxy.example.com:8080".to_owned(),
+                    source: source.clone(),
+                }),
+            },
+        );
+        assert!(!format!("{:?}", config).contains("agent-api-key"));
+
+        let env = HashMap::from([(APIKEY_ENV, "env-api-key".to_owned())]);
+        let config = ClientConfig::from_env_with(|name| env.get(name).cloned()).or(config);
+        assert_eq!(
+            config.api_key.as_ref().unwrap().source,
+            ConfigSource::Env(APIKEY_ENV),
+        );
+        assert_eq!(config.api_base.as_ref().unwrap().source, source);
+        #[cfg(feature = "reqwest")]
+        assert!(config.builder().unwrap().try_build().is_ok());
+        assert!(matches!(
+            ClientConfig::default().builder(),
+            Err(ConfigError::MissingApiKey),
+        ));
+
+        assert!(matches!(
+            ClientConfig::from_agent_config(std::env::temp_dir()),
+            Err(ConfigError::ReadAgentConfigError(..)),
+        ));
+    }
+
+    #[test]
+    fn test_from_agent_config_not_found() {
+        let path = std::env::temp_dir().join(format!(
+            "mackerel-client-rs-test-{}-not-found.conf",
+            std::process::id()
+        ));
+        assert_eq!(
+            ClientConfig::from_agent_config(&path).ok(),
+            Some(ClientConfig::default()),
+        );
+
+        let env = HashMap::from([(APIKEY_ENV, "env-api-key".to_owned())]);
+        let config = ClientConfig::from_env_with(|name| env.get(name).cloned())
+            .or(ClientConfig::from_agent_config(&path).unwrap());
+        assert_eq!(
+            config.api_key.as_ref().unwrap().source,
+            ConfigSource::Env(APIKEY_ENV),
+        );
+    }
+}
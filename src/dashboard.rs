@@ -3,6 +3,7 @@ use http::Method;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::{skip_serializing_none, DurationSeconds};
 use std::borrow::Borrow;
+use strum::VariantNames;
 use typed_builder::TypedBuilder;
 
 use crate::client::*;
@@ -31,11 +32,16 @@ pub struct DashboardValue {
     #[builder(default)]
     #[serde(default)]
     pub widgets: Vec<DashboardWidget>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A dashboard widget
 #[skip_serializing_none]
-#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, VariantNames)]
+#[strum(serialize_all = "camelCase")]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum DashboardWidget {
     #[serde(rename_all = "camelCase")]
@@ -49,6 +55,9 @@ pub enum DashboardWidget {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         reference_lines: Vec<DashboardReferenceLine>,
         layout: DashboardLayout,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Value {
@@ -65,19 +74,35 @@ pub enum DashboardWidget {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         format_rules: Vec<DashboardFormatRule>,
         layout: DashboardLayout,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Markdown {
         title: String,
         markdown: String,
         layout: DashboardLayout,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     AlertStatus {
         title: String,
         role_fullname: RoleFullname,
         layout: DashboardLayout,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    /// A widget of the type not supported by this library.
+    /// The JSON object is preserved, including the `type` field.
+    #[serde(untagged)]
+    Unknown(
+        #[serde(deserialize_with = "crate::entity::deserialize_unknown::<DashboardWidget, _>")]
+        serde_json::Map<String, serde_json::Value>,
+    ),
 }
 
 /// A dashboard graph
@@ -222,6 +247,7 @@ mod tests {
                                 width: 8,
                                 height: 6,
                             },
+                            extra: Default::default(),
                         },
                         DashboardWidget::Graph {
                             title: "Graph title".to_string(),
@@ -242,6 +268,7 @@ mod tests {
                                 width: 8,
                                 height: 6,
                             },
+                            extra: Default::default(),
                         },
                         DashboardWidget::Value {
                             title: "Metric value title".to_string(),
@@ -264,6 +291,7 @@ mod tests {
                                 width: 8,
                                 height: 6,
                             },
+                            extra: Default::default(),
                         },
                         DashboardWidget::Markdown {
                             title: "Markdown title".to_string(),
@@ -274,6 +302,7 @@ mod tests {
                                 width: 8,
                                 height: 6,
                             },
+                            extra: Default::default(),
                         },
                         DashboardWidget::AlertStatus {
                             title: "Alert status title".to_string(),
@@ -284,6 +313,7 @@ mod tests {
                                 width: 8,
                                 height: 6,
                             },
+                            extra: Default::default(),
                         },
                     ])
                     .build(),
@@ -401,13 +431,71 @@ mod tests {
         })
     }
 
+    fn dashboard_with_unknown_widget_example() -> Dashboard {
+        Dashboard::builder()
+            .id("dashboard2")
+            .value(
+                DashboardValue::builder()
+                    .title("This is a dashboard")
+                    .memo("")
+                    .url_path("example")
+                    .widgets([DashboardWidget::Unknown(
+                        json!({
+                            "type": "newWidgetType",
+                            "title": "New widget",
+                            "layout": { "x": 0, "y": 0, "width": 8, "height": 6 },
+                        })
+                        .as_object()
+                        .unwrap()
+                        .clone(),
+                    )])
+                    .extra(
+                        json!({ "newSetting": { "key": "value" } })
+                            .as_object()
+                            .unwrap()
+                            .clone(),
+                    )
+                    .build(),
+            )
+            .build()
+    }
+
+    fn dashboard_with_unknown_widget_json_example() -> serde_json::Value {
+        json!({
+            "id": "dashboard2",
+            "title": "This is a dashboard",
+            "memo": "",
+            "urlPath": "example",
+            "widgets": [
+                {
+                    "type": "newWidgetType",
+                    "title": "New widget",
+                    "layout": { "x": 0, "y": 0, "width": 8, "height": 6 },
+                },
+            ],
+            "newSetting": { "key": "value" },
+        })
+    }
+
     #[rstest]
     #[case(dashboard_example(), json_example())]
+    #[case(
+        dashboard_with_unknown_widget_example(),
+        dashboard_with_unknown_widget_json_example()
+    )]
     fn test_dashboard_json(#[case] dashboard: Dashboard, #[case] json: serde_json::Value) {
         assert_eq!(serde_json::to_value(&dashboard).unwrap(), json);
         assert_eq!(dashboard, serde_json::from_value(json).unwrap());
     }
 
+    #[rstest]
+    #[case(json!({ "type": "markdown", "title": "Example markdown widget", "markdown": 1 }))]
+    #[case(json!({ "type": "alertStatus", "title": "Example alert status widget" }))]
+    #[case(json!({ "title": "Example widget without type" }))]
+    fn test_dashboard_widget_json_malformed(#[case] json: serde_json::Value) {
+        assert!(serde_json::from_value::<DashboardWidget>(json).is_err());
+    }
+
     fn dashboard_graph_example1() -> DashboardGraph {
         DashboardGraph::Host {
             host_id: "host1".into(),
@@ -566,89 +654,108 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the dashboards.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/dashboards#list>.
-    pub async fn list_dashboards(&self) -> Result<Vec<Dashboard>> {
-        self.request(
-            Method::GET,
-            "/api/v0/dashboards",
-            query_params![],
-            request_body![],
-            response_body! { dashboards: Vec<Dashboard> },
-        )
-        .await
-    }
-
-    /// Creates a new dashboard.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/dashboards#create>.
-    pub async fn create_dashboard(
-        &self,
-        dashboard_value: impl Borrow<DashboardValue>,
-    ) -> Result<Dashboard> {
-        self.request(
-            Method::POST,
-            "/api/v0/dashboards",
-            query_params![],
-            request_body!(dashboard_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Gets a dashboard.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/dashboards#get>.
-    pub async fn get_dashboard(&self, dashboard_id: impl Into<DashboardId>) -> Result<Dashboard> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/dashboards/{}", dashboard_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Updates a dashboard.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/dashboards#update>.
-    pub async fn update_dashboard(
-        &self,
-        dashboard_id: impl Into<DashboardId>,
-        dashboard_value: impl Borrow<DashboardValue>,
-    ) -> Result<Dashboard> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/dashboards/{}", dashboard_id),
-            query_params![],
-            request_body!(dashboard_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Deletes a dashboard.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/dashboards#delete>.
-    pub async fn delete_dashboard(
-        &self,
-        dashboard_id: impl Into<DashboardId>,
-    ) -> Result<Dashboard> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/dashboards/{}", dashboard_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Fetches all the dashboards.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/dashboards#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_dashboards(&self) -> Result<Vec<Dashboard>> {
+            self.request(
+                Method::GET,
+                "/api/v0/dashboards",
+                query_params![],
+                request_body![],
+                response_body! { dashboards: Vec<Dashboard> },
+            )
+            .await
+        }
+
+        /// Creates a new dashboard.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/dashboards#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_dashboard(
+            &self,
+            dashboard_value: impl Borrow<DashboardValue>,
+        ) -> Result<Dashboard> {
+            self.request(
+                Method::POST,
+                "/api/v0/dashboards",
+                query_params![],
+                request_body!(dashboard_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Gets a dashboard.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/dashboards#get>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(dashboard_id))
+        )]
+        pub async fn get_dashboard(
+            &self,
+            dashboard_id: impl Into<DashboardId>,
+        ) -> Result<Dashboard> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/dashboards/{}", dashboard_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Updates a dashboard.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/dashboards#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(dashboard_id))
+        )]
+        pub async fn update_dashboard(
+            &self,
+            dashboard_id: impl Into<DashboardId>,
+            dashboard_value: impl Borrow<DashboardValue>,
+        ) -> Result<Dashboard> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/dashboards/{}", dashboard_id),
+                query_params![],
+                request_body!(dashboard_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Deletes a dashboard.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/dashboards#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(dashboard_id))
+        )]
+        pub async fn delete_dashboard(
+            &self,
+            dashboard_id: impl Into<DashboardId>,
+        ) -> Result<Dashboard> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/dashboards/{}", dashboard_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -684,6 +791,7 @@ mod client_tests {
                     width: 8,
                     height: 6,
                 },
+                extra: Default::default(),
             }])
             .build()
     }
@@ -77,6 +77,10 @@ pub struct DowntimeValue {
     )]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub monitor_exclude_scopes: Vec<MonitorId>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A downtime recurrence setting
@@ -271,72 +275,87 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the downtimes.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/downtimes#list>.
-    pub async fn list_downtimes(&self) -> Result<Vec<Downtime>> {
-        self.request(
-            Method::GET,
-            "/api/v0/downtimes",
-            query_params![],
-            request_body![],
-            response_body! { downtimes: Vec<Downtime> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the downtimes.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/downtimes#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_downtimes(&self) -> Result<Vec<Downtime>> {
+            self.request(
+                Method::GET,
+                "/api/v0/downtimes",
+                query_params![],
+                request_body![],
+                response_body! { downtimes: Vec<Downtime> },
+            )
+            .await
+        }
 
-    /// Creates a new downtime.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/downtimes#create>.
-    pub async fn create_downtime(
-        &self,
-        downtime_value: impl Borrow<DowntimeValue>,
-    ) -> Result<Downtime> {
-        self.request(
-            Method::POST,
-            "/api/v0/downtimes",
-            query_params![],
-            request_body!(downtime_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new downtime.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/downtimes#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_downtime(
+            &self,
+            downtime_value: impl Borrow<DowntimeValue>,
+        ) -> Result<Downtime> {
+            self.request(
+                Method::POST,
+                "/api/v0/downtimes",
+                query_params![],
+                request_body!(downtime_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Updates a downtime.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/downtimes#update>.
-    pub async fn update_downtime(
-        &self,
-        downtime_id: impl Into<DowntimeId>,
-        downtime_value: impl Borrow<DowntimeValue>,
-    ) -> Result<Downtime> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/downtimes/{}", downtime_id),
-            query_params![],
-            request_body!(downtime_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Updates a downtime.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/downtimes#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(downtime_id))
+        )]
+        pub async fn update_downtime(
+            &self,
+            downtime_id: impl Into<DowntimeId>,
+            downtime_value: impl Borrow<DowntimeValue>,
+        ) -> Result<Downtime> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/downtimes/{}", downtime_id),
+                query_params![],
+                request_body!(downtime_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes a downtime.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/downtimes#delete>.
-    pub async fn delete_downtime(&self, downtime_id: impl Into<DowntimeId>) -> Result<Downtime> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/downtimes/{}", downtime_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Deletes a downtime.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/downtimes#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(downtime_id))
+        )]
+        pub async fn delete_downtime(
+            &self,
+            downtime_id: impl Into<DowntimeId>,
+        ) -> Result<Downtime> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/downtimes/{}", downtime_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -0,0 +1,185 @@
+//! Dry-run mode of the API client
+//!
+//! In the dry-run mode, the client sends the GET requests as usual,
+//! but does not send the write (non-GET) requests and records them instead.
+//! This is useful to rehearse the scripts against the production organization.
+#![cfg_attr(feature = "reqwest", doc = "```rust")]
+#![cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+//! use mackerel_client::dry_run::DryRunMode;
+//! use mackerel_client::Client;
+//!
+//! let client = Client::builder()
+//!     .api_key("<Mackerel-API-KEY>")
+//!     .dry_run(DryRunMode::Simulate)
+//!     .build();
+//! // Sync the configurations with the client, and then
+//! for request in client.dry_run_requests() {
+//!     println!("{} {} {:?}", request.method, request.path, request.body);
+//! }
+//! ```
+use http::Method;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use url::Url;
+
+/// The mode of handling the write (non-GET) requests in the dry-run mode.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum DryRunMode {
+    /// Refuses the write requests with [`Error::DryRun`](crate::error::Error::DryRun).
+    Refuse,
+    /// Simulates the successful response of the write requests.
+    /// The response is built from the request body with the dummy id [`DRY_RUN_ID`],
+    /// and fails with [`Error::DryRun`](crate::error::Error::DryRun) if the response cannot be simulated
+    /// (for example, the deletion methods returning the deleted entity).
+    Simulate,
+}
+
+/// The dummy id of the simulated responses.
+pub const DRY_RUN_ID: &str = "dry-run";
+
+/// A write request recorded in the dry-run mode.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DryRunRequest {
+    pub method: Method,
+    /// The path and query of the request.
+    pub path: String,
+    /// The JSON request body, or `None` if the request has no body.
+    pub body: Option<Value>,
+}
+
+#[derive(Debug)]
+pub(crate) struct DryRun {
+    mode: DryRunMode,
+    requests: Mutex<Vec<DryRunRequest>>,
+}
+
+impl DryRun {
+    pub(crate) fn new(mode: DryRunMode) -> Self {
+        Self {
+            mode,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn requests(&self) -> Vec<DryRunRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Records the write request, and returns the candidates of the simulated response body.
+    /// The candidates are empty if the request is refused.
+    pub(crate) fn handle(&self, method: &Method, url: &Url, request_body: &[u8]) -> Vec<Value> {
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_owned(),
+        };
+        let body = serde_json::from_slice::<Value>(request_body).ok();
+        self.requests.lock().unwrap().push(DryRunRequest {
+            method: method.clone(),
+            path,
+            body: body.clone(),
+        });
+        match self.mode {
+            DryRunMode::Refuse => Vec::new(),
+            DryRunMode::Simulate => {
+                let mut response_bodies = Vec::new();
+                if let Some(Value::Object(mut fields)) = body {
+                    fields
+                        .entry("id")
+                        .or_insert_with(|| Value::from(DRY_RUN_ID));
+                    response_bodies.push(Value::Object(fields));
+                }
+                response_bodies.push(json!({ "id": DRY_RUN_ID, "success": true }));
+                response_bodies
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::dry_run::*;
+    use crate::error::Error;
+    use crate::host::HostStatus;
+    use crate::monitor::Monitor;
+    use crate::organization::Organization;
+    use crate::test_util::fixtures;
+    use crate::tests::*;
+
+    #[async_std::test]
+    async fn dry_run_refuse() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/org",
+            response = json!({ "name": "ExampleOrganization" }),
+        };
+        let client = crate::Client::builder()
+            .api_key("")
+            .api_base(server.url_str("/"))
+            .dry_run(DryRunMode::Refuse)
+            .build();
+        assert_eq!(
+            client.get_organization().await,
+            Ok(Organization::builder().name("ExampleOrganization").build()),
+        );
+        assert_eq!(
+            client.delete_host_metadata("host1", "namespace0").await,
+            Err(Error::DryRun {
+                method: Method::DELETE,
+                path: "/api/v0/hosts/host1/metadata/namespace0".to_owned(),
+            }),
+        );
+        assert_eq!(
+            client.dry_run_requests(),
+            vec![DryRunRequest {
+                method: Method::DELETE,
+                path: "/api/v0/hosts/host1/metadata/namespace0".to_owned(),
+                body: None,
+            }],
+        );
+    }
+
+    #[async_std::test]
+    async fn dry_run_simulate() {
+        let client = crate::Client::builder()
+            .api_key("")
+            .api_base("https://mackerel.example.com")
+            .dry_run(DryRunMode::Simulate)
+            .build();
+        assert_eq!(
+            client.create_monitor(fixtures::monitor().value).await,
+            Ok(Monitor::new(DRY_RUN_ID, fixtures::monitor().value)),
+        );
+        assert_eq!(
+            client
+                .update_host_status("host1", HostStatus::Maintenance)
+                .await,
+            Ok(()),
+        );
+        assert!(client
+            .delete_monitor("monitor1")
+            .await
+            .is_err_and(|err| matches!(err, Error::DryRun { .. })));
+        assert_eq!(
+            client.dry_run_requests(),
+            vec![
+                DryRunRequest {
+                    method: Method::POST,
+                    path: "/api/v0/monitors".to_owned(),
+                    body: Some(serde_json::to_value(fixtures::monitor().value).unwrap()),
+                },
+                DryRunRequest {
+                    method: Method::POST,
+                    path: "/api/v0/hosts/host1/status".to_owned(),
+                    body: Some(json!({ "status": "maintenance" })),
+                },
+                DryRunRequest {
+                    method: Method::DELETE,
+                    path: "/api/v0/monitors/monitor1".to_owned(),
+                    body: None,
+                },
+            ],
+        );
+    }
+}
@@ -118,6 +118,28 @@ impl<T> std::hash::Hash for Id<T> {
     }
 }
 
+/// Deserializes the JSON object of the type not supported by this library.
+/// The object must have the `type` field, and the type must not be one of the variants of `T`
+/// other than the fallback `unknown`, so that malformed values of the known types are rejected.
+pub(crate) fn deserialize_unknown<'de, T, D>(
+    deserializer: D,
+) -> Result<serde_json::Map<String, serde_json::Value>, D::Error>
+where
+    T: strum::VariantNames,
+    D: serde::Deserializer<'de>,
+{
+    let fields = <serde_json::Map<String, serde_json::Value> as serde::Deserialize>::deserialize(
+        deserializer,
+    )?;
+    match fields.get("type").and_then(serde_json::Value::as_str) {
+        None => Err(serde::de::Error::missing_field("type")),
+        Some(r#type) if r#type != "unknown" && T::VARIANTS.contains(&r#type) => Err(
+            serde::de::Error::custom(format_args!("invalid value of type {}", r#type)),
+        ),
+        Some(_) => Ok(fields),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
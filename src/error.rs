@@ -1,21 +1,461 @@
 use derivative::Derivative;
-use http::StatusCode;
+use http::{Method, StatusCode};
+use std::collections::BTreeMap;
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::expression::ExpressionError;
+
 /// Error represents the error type of the library.
 #[derive(Debug, Derivative, Error)]
 #[derivative(PartialEq)]
 pub enum Error {
-    #[error("status_code:{0}, message:{1}")]
-    ApiError(StatusCode, String),
+    /// The resource is not found (404).
+    #[error("not found: {0}")]
+    NotFound(ApiErrorDetail),
 
-    #[error(transparent)]
-    RequestError(
-        #[from]
+    /// The API key is missing or invalid (401).
+    #[error("unauthorized: {0}")]
+    Unauthorized(ApiErrorDetail),
+
+    /// The API key does not have the permission (403).
+    #[error("forbidden: {0}")]
+    Forbidden(ApiErrorDetail),
+
+    /// The request conflicts with the current state of the resource (409).
+    #[error("conflict: {0}")]
+    Conflict(ApiErrorDetail),
+
+    /// The request is rate limited (429).
+    /// The `Retry-After` header is available via [`Error::retry_after`].
+    #[error("rate limited: {0}")]
+    RateLimited(ApiErrorDetail),
+
+    /// The request is invalid (400, 422).
+    /// The field-level messages are available via [`ApiErrorDetail::field_errors`].
+    #[error("validation error: {0}")]
+    ValidationError(ApiErrorDetail),
+
+    /// The server failed to process the request (5xx).
+    #[error("server error: {0}")]
+    ServerError(ApiErrorDetail),
+
+    /// The API responded with other error status.
+    #[error("api error: {0}")]
+    ApiError(ApiErrorDetail),
+
+    /// The request timed out.
+    #[error("timeout: {0}")]
+    Timeout(#[derivative(PartialEq = "ignore")] TransportError),
+
+    /// The response body could not be decoded.
+    #[error("failed to decode response of {method} {path}: {source}")]
+    DecodeError {
+        method: Method,
+        path: String,
+        body: String,
         #[derivative(PartialEq = "ignore")]
-        reqwest::Error,
-    ),
+        source: serde_json::Error,
+    },
+
+    /// The write request is not sent in the dry-run mode.
+    /// See [`crate::dry_run`] for details.
+    #[error("dry run: {method} {path} is not sent")]
+    DryRun { method: Method, path: String },
+
+    /// The graph expression is invalid for the evaluation.
+    /// See [`crate::expression`] for details.
+    #[error("invalid expression: {0}")]
+    InvalidExpression(#[from] ExpressionError),
+
+    #[error(transparent)]
+    RequestError(#[derivative(PartialEq = "ignore")] TransportError),
+}
+
+impl From<TransportError> for Error {
+    fn from(err: TransportError) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else {
+            Self::RequestError(err)
+        }
+    }
+}
+
+impl Error {
+    /// Returns the detail of the error response of the API.
+    pub fn api_error_detail(&self) -> Option<&ApiErrorDetail> {
+        match self {
+            Self::NotFound(detail)
+            | Self::Unauthorized(detail)
+            | Self::Forbidden(detail)
+            | Self::Conflict(detail)
+            | Self::RateLimited(detail)
+            | Self::ValidationError(detail)
+            | Self::ServerError(detail)
+            | Self::ApiError(detail) => Some(detail),
+            _ => None,
+        }
+    }
+
+    /// Returns the status code of the error response of the API.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.api_error_detail().map(|detail| detail.status)
+    }
+
+    /// Returns the duration of the `Retry-After` header of the error response.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.api_error_detail()
+            .and_then(|detail| detail.retry_after)
+    }
+
+    /// Returns `true` if the error is [`Error::NotFound`].
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+
+    /// Returns `true` if the error is [`Error::Unauthorized`] or [`Error::Forbidden`].
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, Self::Unauthorized(_) | Self::Forbidden(_))
+    }
+
+    /// Returns `true` if the request may succeed by retrying.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited(_) | Self::Timeout(_) => true,
+            Self::ServerError(detail) => matches!(
+                detail.status,
+                StatusCode::INTERNAL_SERVER_ERROR
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Self::RequestError(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// TransportError represents the error on sending the request by [`Transport`](crate::transport::Transport).
+#[derive(Debug, Error)]
+#[error("{source}")]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+/// The kind of [`TransportError`].
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum TransportErrorKind {
+    /// Failed to connect to the server.
+    Connect,
+    /// The request timed out.
+    Timeout,
+    /// Failed to send the request after connecting, e.g. the connection was reset.
+    Request,
+    /// Failed to read the response body.
+    Body,
+    /// The other errors.
+    Other,
+}
+
+impl TransportError {
+    /// Creates a new [`TransportError`].
+    pub fn new(
+        kind: TransportErrorKind,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Creates a new [`TransportError`] of [`TransportErrorKind::Other`].
+    pub fn other(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::new(TransportErrorKind::Other, source)
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    /// Returns `true` if the error is [`TransportErrorKind::Connect`].
+    pub fn is_connect(&self) -> bool {
+        self.kind == TransportErrorKind::Connect
+    }
+
+    /// Returns `true` if the error is [`TransportErrorKind::Timeout`].
+    pub fn is_timeout(&self) -> bool {
+        self.kind == TransportErrorKind::Timeout
+    }
+
+    /// Returns `true` if the error is [`TransportErrorKind::Request`].
+    pub fn is_request(&self) -> bool {
+        self.kind == TransportErrorKind::Request
+    }
+
+    /// Returns `true` if the error is [`TransportErrorKind::Body`].
+    pub fn is_body(&self) -> bool {
+        self.kind == TransportErrorKind::Body
+    }
+
+    /// Returns `true` if the request may succeed by retrying.
+    /// The request may have reached the server unless the error is on connecting,
+    /// so retry only the idempotent requests.
+    pub fn is_retryable(&self) -> bool {
+        self.is_connect() || self.is_timeout() || self.is_request() || self.is_body()
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if err.is_connect() {
+            TransportErrorKind::Connect
+        } else if err.is_body() || err.is_decode() {
+            TransportErrorKind::Body
+        } else if err.is_request() {
+            TransportErrorKind::Request
+        } else {
+            TransportErrorKind::Other
+        };
+        Self::new(kind, err)
+    }
+}
+
+/// The detail of the error response of the API.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ApiErrorDetail {
+    /// The method of the request.
+    pub method: Method,
+    /// The path of the request.
+    pub path: String,
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The error message extracted from the response body.
+    pub message: String,
+    /// The field-level error messages extracted from the response body.
+    pub field_errors: BTreeMap<String, Vec<String>>,
+    /// The duration of the `Retry-After` response header.
+    pub retry_after: Option<Duration>,
+    /// The raw response body.
+    pub body: String,
+}
+
+impl std::fmt::Display for ApiErrorDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}, status_code:{}, message:{}",
+            self.method, self.path, self.status, self.message
+        )
+    }
+}
+
+impl ApiErrorDetail {
+    /// Parses the error response body of the API.
+    /// The body is either `{"error":"message"}` or `{"error":{"message":"message"}}`,
+    /// and the other entries of the error object are regarded as the field-level messages.
+    pub(crate) fn new(
+        method: Method,
+        path: impl Into<String>,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        body: String,
+    ) -> Self {
+        let error_opt = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|mut value| value.get_mut("error").map(serde_json::Value::take));
+        let message = error_opt
+            .as_ref()
+            .and_then(|err| err.get("message").unwrap_or(err).as_str())
+            .map(str::to_owned)
+            .unwrap_or_else(|| body.clone());
+        let field_errors = error_opt
+            .as_ref()
+            .and_then(serde_json::Value::as_object)
+            .map(|object| {
+                object
+                    .iter()
+                    .filter(|(key, _)| *key != "message")
+                    .filter_map(|(key, value)| {
+                        let messages = match value {
+                            serde_json::Value::String(message) => vec![message.clone()],
+                            serde_json::Value::Array(values) => values
+                                .iter()
+                                .filter_map(serde_json::Value::as_str)
+                                .map(str::to_owned)
+                                .collect(),
+                            _ => return None,
+                        };
+                        Some((key.clone(), messages))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            method,
+            path: path.into(),
+            status,
+            message,
+            field_errors,
+            retry_after,
+            body,
+        }
+    }
+
+    /// Converts into the [`Error`] corresponding to the status code.
+    pub(crate) fn into_error(self) -> Error {
+        match self.status {
+            StatusCode::NOT_FOUND => Error::NotFound(self),
+            StatusCode::UNAUTHORIZED => Error::Unauthorized(self),
+            StatusCode::FORBIDDEN => Error::Forbidden(self),
+            StatusCode::CONFLICT => Error::Conflict(self),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited(self),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                Error::ValidationError(self)
+            }
+            status if status.is_server_error() => Error::ServerError(self),
+            _ => Error::ApiError(self),
+        }
+    }
+}
+
+/// ConfigError represents the error on building the API client.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("api_key is not configured")]
+    MissingApiKey,
+
+    #[error("invalid api_key: {0}")]
+    InvalidApiKey(#[source] http::header::InvalidHeaderValue),
+
+    #[error("invalid api_base ({0:?}): {1}")]
+    InvalidApiBase(String, #[source] url::ParseError),
+
+    #[error("invalid user_agent ({0:?}): {1}")]
+    InvalidUserAgent(String, #[source] http::header::InvalidHeaderValue),
+
+    #[cfg(feature = "reqwest")]
+    #[error("invalid proxy ({0:?}): {1}")]
+    InvalidProxy(String, #[source] reqwest::Error),
+
+    #[error("failed to read agent config ({0:?}): {1}")]
+    ReadAgentConfigError(std::path::PathBuf, #[source] std::io::Error),
+
+    #[error("failed to parse agent config ({0:?}): {1}")]
+    ParseAgentConfigError(std::path::PathBuf, #[source] toml::de::Error),
+
+    #[error("failed to read cassette ({0:?}): {1}")]
+    ReadCassetteError(std::path::PathBuf, #[source] std::io::Error),
+
+    #[error("failed to parse cassette ({0:?}): {1}")]
+    ParseCassetteError(std::path::PathBuf, #[source] serde_json::Error),
+
+    #[cfg(feature = "reqwest")]
+    #[error("failed to build http client: {0}")]
+    HttpClientError(#[source] reqwest::Error),
+
+    #[error("transport is not configured")]
+    MissingTransport,
 }
 
 /// Result alias where the error type is [`crate::Error`].
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn detail(status: StatusCode, body: serde_json::Value) -> ApiErrorDetail {
+        ApiErrorDetail::new(Method::GET, "/api/v0/test", status, None, body.to_string())
+    }
+
+    #[rstest]
+    #[case(json!({ "error": "error message" }), "error message")]
+    #[case(json!({ "error": { "message": "error message" } }), "error message")]
+    #[case(json!("error message"), r#""error message""#)]
+    #[case(json!({ "message": "error message" }), r#"{"message":"error message"}"#)]
+    fn test_api_error_message(#[case] body: serde_json::Value, #[case] message: &str) {
+        assert_eq!(detail(StatusCode::BAD_REQUEST, body).message, message);
+    }
+
+    #[test]
+    fn test_api_error_field_errors() {
+        let detail = detail(
+            StatusCode::BAD_REQUEST,
+            json!({
+                "error": {
+                    "message": "invalid parameters",
+                    "name": "name is required",
+                    "scopes": ["invalid scope: foo", "invalid scope: bar"],
+                    "code": 1,
+                },
+            }),
+        );
+        assert_eq!(detail.message, "invalid parameters");
+        assert_eq!(
+            detail.field_errors,
+            BTreeMap::from([
+                ("name".to_owned(), vec!["name is required".to_owned()]),
+                (
+                    "scopes".to_owned(),
+                    vec![
+                        "invalid scope: foo".to_owned(),
+                        "invalid scope: bar".to_owned()
+                    ]
+                ),
+            ]),
+        );
+    }
+
+    #[rstest]
+    #[case(StatusCode::NOT_FOUND, Error::NotFound, false)]
+    #[case(StatusCode::UNAUTHORIZED, Error::Unauthorized, false)]
+    #[case(StatusCode::FORBIDDEN, Error::Forbidden, false)]
+    #[case(StatusCode::CONFLICT, Error::Conflict, false)]
+    #[case(StatusCode::TOO_MANY_REQUESTS, Error::RateLimited, true)]
+    #[case(StatusCode::BAD_REQUEST, Error::ValidationError, false)]
+    #[case(StatusCode::UNPROCESSABLE_ENTITY, Error::ValidationError, false)]
+    #[case(StatusCode::INTERNAL_SERVER_ERROR, Error::ServerError, true)]
+    #[case(StatusCode::SERVICE_UNAVAILABLE, Error::ServerError, true)]
+    #[case(StatusCode::NOT_IMPLEMENTED, Error::ServerError, false)]
+    #[case(StatusCode::GONE, Error::ApiError, false)]
+    fn test_api_error_into_error(
+        #[case] status: StatusCode,
+        #[case] variant: fn(ApiErrorDetail) -> Error,
+        #[case] is_retryable: bool,
+    ) {
+        let detail = detail(status, json!({ "error": "error message" }));
+        let error = detail.clone().into_error();
+        assert_eq!(error, variant(detail.clone()));
+        assert_eq!(error.status(), Some(status));
+        assert_eq!(error.api_error_detail(), Some(&detail));
+        assert_eq!(error.is_retryable(), is_retryable);
+        assert_eq!(error.is_not_found(), status == StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[case(TransportErrorKind::Connect, true)]
+    #[case(TransportErrorKind::Timeout, true)]
+    #[case(TransportErrorKind::Request, true)]
+    #[case(TransportErrorKind::Body, true)]
+    #[case(TransportErrorKind::Other, false)]
+    fn test_transport_error_into_error(
+        #[case] kind: TransportErrorKind,
+        #[case] is_retryable: bool,
+    ) {
+        let error = Error::from(TransportError::new(kind, "transport error"));
+        assert_eq!(
+            matches!(error, Error::Timeout(_)),
+            kind == TransportErrorKind::Timeout,
+        );
+        assert_eq!(error.is_retryable(), is_retryable);
+    }
+}
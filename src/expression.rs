@@ -0,0 +1,1455 @@
+//! Parser, validator and evaluator of the graph expressions
+//!
+//! The graph expressions are used in the expression monitors and the expression graphs of dashboards.
+//! See <https://mackerel.io/docs/entry/advanced/advanced-graph> for the functions.
+//! [`Client::evaluate_expression`] computes the time series locally from the fetched metric values.
+//! ```rust
+//! use mackerel_client::expression::Expression;
+//!
+//! let expression: Expression = "avg(roleSlots('service:role', 'loadavg5'))".parse().unwrap();
+//! assert_eq!(expression.validate(), Ok(()));
+//! assert_eq!(expression.to_string(), r#"avg(roleSlots("service:role", "loadavg5"))"#);
+//!
+//! let err = "avg(roleSlots('service:role', 'loadavg5')".parse::<Expression>().unwrap_err();
+//! assert_eq!(err.to_string(), "expected , or ) at position 41");
+//! ```
+use chrono::{DateTime, TimeDelta, Utc};
+use derivative::Derivative;
+use futures_util::future::{BoxFuture, FutureExt};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::client::*;
+use crate::error::{Error, Result};
+use crate::host::{HostId, ListHostsParams};
+use crate::metric::MetricValue;
+use crate::monitor::match_name_pattern;
+use crate::role::RoleFullname;
+use crate::service::ServiceName;
+
+const METRICS_FETCH_CONCURRENCY: usize = 5;
+
+/// A node of the graph expression.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    /// The byte range of the node in the source, ignored on comparison.
+    #[derivative(PartialEq = "ignore")]
+    pub span: Range<usize>,
+}
+
+/// The kind of [`Expression`].
+#[derive(PartialEq, Clone, Debug)]
+pub enum ExpressionKind {
+    /// A function call, for example `host('host0', 'loadavg5')`.
+    Call { name: String, args: Vec<Expression> },
+    /// A string literal quoted by `'` or `"`.
+    String(String),
+    /// A number literal.
+    Number(f64),
+}
+
+/// The error of parsing or validating the graph expression.
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+#[error("{message} at position {position}")]
+pub struct ExpressionError {
+    pub message: String,
+    /// The byte offset in the source.
+    pub position: usize,
+}
+
+impl ExpressionError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl Expression {
+    /// Creates a function call node.
+    pub fn call(name: impl Into<String>, args: impl IntoIterator<Item = Expression>) -> Self {
+        Self::from(ExpressionKind::Call {
+            name: name.into(),
+            args: args.into_iter().collect(),
+        })
+    }
+
+    /// Creates a string literal node.
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::from(ExpressionKind::String(value.into()))
+    }
+
+    /// Creates a number literal node.
+    pub fn number(value: f64) -> Self {
+        Self::from(ExpressionKind::Number(value))
+    }
+
+    /// Parses the graph expression.
+    pub fn parse(source: &str) -> std::result::Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+        };
+        let expression = parser.expression()?;
+        parser.skip_whitespace();
+        if parser.position < source.len() {
+            return Err(ExpressionError::new(
+                "unexpected trailing input",
+                parser.position,
+            ));
+        }
+        Ok(expression)
+    }
+
+    /// Validates the functions known to this library and their arguments, and returns all the errors.
+    /// The functions not known to this library are not regarded as errors,
+    /// but reported by [`Expression::warnings`].
+    pub fn validate(&self) -> std::result::Result<(), Vec<ExpressionError>> {
+        let mut errors = Vec::new();
+        validate_series(self, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the warnings of the functions not known to this library, which are not validated.
+    pub fn warnings(&self) -> Vec<ExpressionError> {
+        let mut warnings = Vec::new();
+        collect_warnings(self, &mut warnings);
+        warnings
+    }
+}
+
+impl From<ExpressionKind> for Expression {
+    fn from(kind: ExpressionKind) -> Self {
+        Self { kind, span: 0..0 }
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Formats the expression in the canonical form, or indents the nested calls with `{:#}`.
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            self.fmt_indent(f, 0)
+        } else {
+            self.fmt_indent(f, usize::MAX)
+        }
+    }
+}
+
+impl Expression {
+    fn fmt_indent(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        match self.kind {
+            ExpressionKind::Call { ref name, ref args } => {
+                write!(f, "{}(", name)?;
+                let nested = indent != usize::MAX
+                    && args
+                        .iter()
+                        .any(|arg| matches!(arg.kind, ExpressionKind::Call { .. }));
+                for (i, arg) in args.iter().enumerate() {
+                    if nested {
+                        write!(f, "{}\n{:2$}", if i > 0 { "," } else { "" }, "", indent + 2)?;
+                        arg.fmt_indent(f, indent + 2)?;
+                    } else {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        arg.fmt_indent(f, indent)?;
+                    }
+                }
+                if nested {
+                    write!(f, "\n{:1$}", "", indent)?;
+                }
+                write!(f, ")")
+            }
+            ExpressionKind::String(ref value) => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    if matches!(c, '"' | '\\') {
+                        write!(f, "\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                write!(f, "\"")
+            }
+            ExpressionKind::Number(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn expression(&mut self) -> std::result::Result<Expression, ExpressionError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let kind = match self.peek() {
+            Some(quote @ ('\'' | '"')) => self.string(quote)?,
+            Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => self.number()?,
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => self.call()?,
+            Some(c) => {
+                return Err(ExpressionError::new(
+                    format!("unexpected character {}", c),
+                    start,
+                ))
+            }
+            None => return Err(ExpressionError::new("unexpected end of expression", start)),
+        };
+        Ok(Expression {
+            kind,
+            span: start..self.position,
+        })
+    }
+
+    fn string(&mut self, quote: char) -> std::result::Result<ExpressionKind, ExpressionError> {
+        let start = self.position;
+        self.position += quote.len_utf8();
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some(c) if c == quote => {
+                    self.position += c.len_utf8();
+                    return Ok(ExpressionKind::String(value));
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some(c) => {
+                            self.position += c.len_utf8();
+                            value.push(c);
+                        }
+                        None => break,
+                    }
+                }
+                Some(c) => {
+                    self.position += c.len_utf8();
+                    value.push(c);
+                }
+                None => break,
+            }
+        }
+        Err(ExpressionError::new("unterminated string", start))
+    }
+
+    fn number(&mut self) -> std::result::Result<ExpressionKind, ExpressionError> {
+        let start = self.position;
+        let len = self.source[start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')))
+            .unwrap_or(self.source.len() - start);
+        let s = &self.source[start..start + len];
+        let value = s
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| ExpressionError::new(format!("invalid number {}", s), start))?;
+        self.position += len;
+        Ok(ExpressionKind::Number(value))
+    }
+
+    fn call(&mut self) -> std::result::Result<ExpressionKind, ExpressionError> {
+        let start = self.position;
+        let len = self.source[start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(self.source.len() - start);
+        let name = self.source[start..start + len].to_owned();
+        self.position += len;
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return Err(ExpressionError::new(
+                format!("expected ( after {}", name),
+                self.position,
+            ));
+        }
+        self.position += 1;
+        let mut args = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                self.position += 1;
+                break;
+            }
+            args.push(self.expression()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(')') => {
+                    self.position += 1;
+                    break;
+                }
+                _ => return Err(ExpressionError::new("expected , or )", self.position)),
+            }
+        }
+        Ok(ExpressionKind::Call { name, args })
+    }
+}
+
+/// The kind of the function argument.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum ArgKind {
+    Series,
+    HostId,
+    ServiceName,
+    RoleFullname,
+    MetricName,
+    String,
+    Number,
+    Duration,
+}
+
+impl std::fmt::Display for ArgKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Series => "series",
+            Self::HostId => "host id",
+            Self::ServiceName => "service name",
+            Self::RoleFullname => "role fullname",
+            Self::MetricName => "metric name",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Duration => "duration",
+        })
+    }
+}
+
+/// The arguments following the leading arguments of the function.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum RestArgs {
+    /// No more arguments are allowed.
+    None,
+    /// The arguments of the kind are repeated.
+    Repeat(ArgKind),
+    /// The arguments are not checked by this library, except for the nested function calls.
+    Unchecked,
+}
+
+/// Returns the kinds of the leading arguments of the function known to this library,
+/// and the kind of the following arguments.
+/// The other functions are reported by [`Expression::warnings`] instead of [`Expression::validate`].
+fn function_signature(name: &str) -> Option<(&'static [ArgKind], RestArgs)> {
+    use ArgKind::*;
+    Some(match name {
+        "host" => (&[HostId, MetricName], RestArgs::None),
+        "service" => (&[ServiceName, MetricName], RestArgs::None),
+        "role" | "roleSlots" => (&[RoleFullname, MetricName], RestArgs::None),
+        "group" => (&[Series], RestArgs::Repeat(Series)),
+        "avg" | "max" | "min" | "sum" | "stack" => (&[Series], RestArgs::None),
+        "alias" => (&[Series, String], RestArgs::None),
+        "scale" | "offset" | "percentile" => (&[Series, Number], RestArgs::None),
+        "timeShift" | "movingAverage" => (&[Series, Duration], RestArgs::None),
+        "divide" => (&[Series, Series], RestArgs::None),
+        "diff" | "product" => (&[Series], RestArgs::Unchecked),
+        "linearRegression" => (&[Series, Duration], RestArgs::Unchecked),
+        _ => return None,
+    })
+}
+
+fn validate_series(expression: &Expression, errors: &mut Vec<ExpressionError>) {
+    let ExpressionKind::Call { ref name, ref args } = expression.kind else {
+        errors.push(ExpressionError::new(
+            "expected a function call",
+            expression.span.start,
+        ));
+        return;
+    };
+    let (arg_kinds, rest) = function_signature(name).unwrap_or((&[], RestArgs::Unchecked));
+    if args.len() < arg_kinds.len() || rest == RestArgs::None && args.len() > arg_kinds.len() {
+        errors.push(ExpressionError::new(
+            format!(
+                "{} expects {}{} arguments, but got {}",
+                name,
+                if rest == RestArgs::None {
+                    ""
+                } else {
+                    "at least "
+                },
+                arg_kinds.len(),
+                args.len()
+            ),
+            expression.span.start,
+        ));
+    }
+    for (i, arg) in args.iter().enumerate() {
+        match (arg_kinds.get(i), rest) {
+            (Some(&arg_kind), _) | (None, RestArgs::Repeat(arg_kind)) => {
+                validate_arg(arg, arg_kind, errors)
+            }
+            (None, RestArgs::None) => break,
+            (None, RestArgs::Unchecked) => {
+                if let ExpressionKind::Call { .. } = arg.kind {
+                    validate_series(arg, errors);
+                }
+            }
+        }
+    }
+}
+
+fn collect_warnings(expression: &Expression, warnings: &mut Vec<ExpressionError>) {
+    if let ExpressionKind::Call { ref name, ref args } = expression.kind {
+        if function_signature(name).is_none() {
+            warnings.push(ExpressionError::new(
+                format!("unknown function {}", name),
+                expression.span.start,
+            ));
+        }
+        for arg in args {
+            collect_warnings(arg, warnings);
+        }
+    }
+}
+
+/// Checks that the functions and their arguments are supported by [`Client::evaluate_expression`].
+fn check_evaluable(expression: &Expression) -> std::result::Result<(), ExpressionError> {
+    let ExpressionKind::Call { ref name, ref args } = expression.kind else {
+        return Ok(());
+    };
+    match function_signature(name) {
+        None => Err(ExpressionError::new(
+            format!(
+                "unknown function {} is not supported by the evaluation",
+                name
+            ),
+            expression.span.start,
+        )),
+        Some((arg_kinds, RestArgs::Unchecked)) if args.len() > arg_kinds.len() => {
+            Err(ExpressionError::new(
+                format!(
+                    "{} with {} arguments is not supported by the evaluation",
+                    name,
+                    args.len()
+                ),
+                expression.span.start,
+            ))
+        }
+        _ => args.iter().try_for_each(check_evaluable),
+    }
+}
+
+fn validate_arg(arg: &Expression, arg_kind: ArgKind, errors: &mut Vec<ExpressionError>) {
+    let position = arg.span.start;
+    match (arg_kind, &arg.kind) {
+        (ArgKind::Series, _) => validate_series(arg, errors),
+        (ArgKind::Number, ExpressionKind::Number(_)) => {}
+        (ArgKind::Number, _) => {
+            errors.push(ExpressionError::new("expected a number", position));
+        }
+        (_, ExpressionKind::String(value)) => {
+            let valid = match arg_kind {
+                ArgKind::HostId | ArgKind::MetricName => !value.is_empty(),
+                ArgKind::ServiceName => value.parse::<ServiceName>().is_ok(),
+                ArgKind::RoleFullname => value.parse::<RoleFullname>().is_ok(),
+                ArgKind::Duration => is_duration(value),
+                _ => true,
+            };
+            if !valid {
+                errors.push(ExpressionError::new(
+                    format!("invalid {} {:?}", arg_kind, value),
+                    position,
+                ));
+            }
+        }
+        (_, _) => {
+            errors.push(ExpressionError::new(
+                format!("expected a string of {}", arg_kind),
+                position,
+            ));
+        }
+    }
+}
+
+fn is_duration(s: &str) -> bool {
+    parse_duration(s).is_some()
+}
+
+/// Parses the duration of digits followed by `m`, `h`, `d` or `w`.
+/// Returns `None` if the duration is malformed or out of the range of [`TimeDelta`].
+fn parse_duration(s: &str) -> Option<TimeDelta> {
+    let digits = s.strip_suffix(['m', 'h', 'd', 'w'])?;
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let value = digits.parse::<i64>().ok()?;
+    match &s[digits.len()..] {
+        "m" => TimeDelta::try_minutes(value),
+        "h" => TimeDelta::try_hours(value),
+        "d" => TimeDelta::try_days(value),
+        _ => TimeDelta::try_weeks(value),
+    }
+}
+
+/// A time series computed by [`Client::evaluate_expression`].
+#[derive(PartialEq, Clone, Debug)]
+pub struct TimeSeries {
+    /// The name of the series, for example `host("host0", "loadavg5")` or the alias.
+    pub name: String,
+    pub values: Vec<MetricValue>,
+}
+
+impl TimeSeries {
+    fn new(name: impl Into<String>, values: Vec<MetricValue>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+
+    fn map_values(self, name: String, f: impl Fn(f64) -> f64) -> Self {
+        let values = self
+            .values
+            .into_iter()
+            .map(|metric_value| MetricValue {
+                time: metric_value.time,
+                value: f(metric_value.value),
+            })
+            .collect();
+        Self::new(name, values)
+    }
+}
+
+/// Accessors of the validated function call.
+impl Expression {
+    fn name(&self) -> &str {
+        match self.kind {
+            ExpressionKind::Call { ref name, .. } => name,
+            _ => "",
+        }
+    }
+
+    fn args(&self) -> &[Expression] {
+        match self.kind {
+            ExpressionKind::Call { ref args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    fn string_arg(&self, index: usize) -> &str {
+        match self.args()[index].kind {
+            ExpressionKind::String(ref value) => value,
+            _ => "",
+        }
+    }
+
+    fn number_arg(&self, index: usize) -> f64 {
+        match self.args()[index].kind {
+            ExpressionKind::Number(value) => value,
+            _ => f64::NAN,
+        }
+    }
+
+    fn duration_arg(&self, index: usize) -> std::result::Result<TimeDelta, ExpressionError> {
+        parse_duration(self.string_arg(index)).ok_or_else(|| self.invalid_duration(index))
+    }
+
+    /// Shifts the time by the duration argument, backward if `backward` is `true`.
+    fn shift_time(
+        &self,
+        index: usize,
+        time: DateTime<Utc>,
+        backward: bool,
+    ) -> std::result::Result<DateTime<Utc>, ExpressionError> {
+        let duration = self.duration_arg(index)?;
+        if backward {
+            time.checked_sub_signed(duration)
+        } else {
+            time.checked_add_signed(duration)
+        }
+        .ok_or_else(|| self.invalid_duration(index))
+    }
+
+    fn invalid_duration(&self, index: usize) -> ExpressionError {
+        ExpressionError::new(
+            format!("invalid {} {:?}", ArgKind::Duration, self.string_arg(index)),
+            self.args()[index].span.start,
+        )
+    }
+}
+
+client_methods! {
+    impl Client {
+        /// Evaluates the graph expression locally over the metric values from `from` to `to`.
+        /// The `host`, `service`, `role` and `roleSlots` functions fetch the metric values,
+        /// where `*` in the metric name matches any metric names.
+        /// Note that `roleSlots` is evaluated as `role`, which yields the series of each host.
+        ///
+        /// Returns [`Error::InvalidExpression`] with the first error of [`Expression::validate`],
+        /// when the expression contains the functions not known to this library
+        /// or the arguments of them not supported by the evaluation,
+        /// or when the duration shifts the times out of the supported range.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn evaluate_expression(
+            &self,
+            expression: impl Borrow<Expression>,
+            from: impl Into<DateTime<Utc>>,
+            to: impl Into<DateTime<Utc>>,
+        ) -> Result<Vec<TimeSeries>> {
+            let expression = expression.borrow();
+            if let Err(mut errors) = expression.validate() {
+                return Err(Error::InvalidExpression(errors.remove(0)));
+            }
+            check_evaluable(expression)?;
+            self.evaluate_series(expression, from.into(), to.into())
+                .await
+        }
+    }
+}
+
+impl Client {
+    fn evaluate_series<'a>(
+        &'a self,
+        expression: &'a Expression,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<TimeSeries>>> {
+        async move {
+            match expression.name() {
+                "host" => {
+                    let host_id = HostId::from(expression.string_arg(0));
+                    self.evaluate_host(host_id, expression.string_arg(1), from, to)
+                        .await
+                }
+                "role" | "roleSlots" => {
+                    let role_fullname = expression
+                        .string_arg(0)
+                        .parse::<RoleFullname>()
+                        .unwrap_or_else(|_| unreachable!("validated role fullname"));
+                    let hosts = self
+                        .list_hosts(ListHostsParams::role_fullname(role_fullname))
+                        .await?;
+                    let series = stream::iter(hosts)
+                        .map(|host| self.evaluate_host(host.id, expression.string_arg(1), from, to))
+                        .buffered(METRICS_FETCH_CONCURRENCY)
+                        .try_collect::<Vec<_>>()
+                        .await?;
+                    Ok(series.into_iter().flatten().collect())
+                }
+                "service" => {
+                    let service_name = expression
+                        .string_arg(0)
+                        .parse::<ServiceName>()
+                        .unwrap_or_else(|_| unreachable!("validated service name"));
+                    let metric_name = expression.string_arg(1);
+                    let metric_names = if metric_name.contains('*') {
+                        expand_metric_names(
+                            metric_name,
+                            self.list_service_metric_names(service_name).await?,
+                        )
+                    } else {
+                        vec![metric_name.to_owned()]
+                    };
+                    stream::iter(metric_names)
+                        .map(|metric_name| async move {
+                            let values = self
+                                .list_service_metric_values(service_name, &metric_name, from, to)
+                                .await?;
+                            let name = Expression::call(
+                                "service",
+                                [
+                                    Expression::string(service_name.to_string()),
+                                    Expression::string(metric_name),
+                                ],
+                            );
+                            Ok(TimeSeries::new(name.to_string(), values))
+                        })
+                        .buffered(METRICS_FETCH_CONCURRENCY)
+                        .try_collect()
+                        .await
+                }
+                "timeShift" => {
+                    let series = self
+                        .evaluate_series(
+                            &expression.args()[0],
+                            expression.shift_time(1, from, true)?,
+                            expression.shift_time(1, to, true)?,
+                        )
+                        .await?;
+                    Ok(apply_function(expression, vec![series], from, to)?)
+                }
+                "linearRegression" => {
+                    let series = self
+                        .evaluate_series(
+                            &expression.args()[0],
+                            from.min(expression.shift_time(1, to, true)?),
+                            to,
+                        )
+                        .await?;
+                    Ok(apply_function(expression, vec![series], from, to)?)
+                }
+                _ => {
+                    let mut series_args = Vec::new();
+                    for arg in expression.args() {
+                        if let ExpressionKind::Call { .. } = arg.kind {
+                            series_args.push(self.evaluate_series(arg, from, to).await?);
+                        }
+                    }
+                    Ok(apply_function(expression, series_args, from, to)?)
+                }
+            }
+        }
+        .boxed()
+    }
+
+    async fn evaluate_host(
+        &self,
+        host_id: HostId,
+        metric_name: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<TimeSeries>> {
+        let metric_names = if metric_name.contains('*') {
+            expand_metric_names(metric_name, self.list_host_metric_names(host_id).await?)
+        } else {
+            vec![metric_name.to_owned()]
+        };
+        stream::iter(metric_names)
+            .map(|metric_name| async move {
+                let values = self
+                    .list_host_metric_values(host_id, &metric_name, from, to)
+                    .await?;
+                let name = Expression::call(
+                    "host",
+                    [
+                        Expression::string(host_id.to_string()),
+                        Expression::string(metric_name),
+                    ],
+                );
+                Ok(TimeSeries::new(name.to_string(), values))
+            })
+            .buffered(METRICS_FETCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+}
+
+fn expand_metric_names(metric_name: &str, metric_names: Vec<String>) -> Vec<String> {
+    let mut metric_names = metric_names
+        .into_iter()
+        .filter(|name| match_name_pattern(metric_name, name))
+        .collect::<Vec<_>>();
+    metric_names.sort();
+    metric_names
+}
+
+/// Applies the function of the expression to the evaluated series of the arguments.
+/// The series of `timeShift` are evaluated over the shifted range,
+/// and the series of `linearRegression` are evaluated over the range including the duration.
+fn apply_function(
+    expression: &Expression,
+    series_args: Vec<Vec<TimeSeries>>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> std::result::Result<Vec<TimeSeries>, ExpressionError> {
+    let mut series_args = series_args.into_iter();
+    let mut series = series_args.next().unwrap_or_default();
+    let name = expression.name();
+    match name {
+        "group" => series.extend(series_args.flatten()),
+        "stack" => {}
+        "avg" => {
+            series = aggregate(expression, series, |values| {
+                values.iter().sum::<f64>() / values.len() as f64
+            })
+        }
+        "max" => {
+            series = aggregate(expression, series, |values| {
+                values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+            })
+        }
+        "min" => {
+            series = aggregate(expression, series, |values| {
+                values.iter().copied().fold(f64::INFINITY, f64::min)
+            })
+        }
+        "sum" => series = aggregate(expression, series, |values| values.iter().sum()),
+        "product" => series = aggregate(expression, series, |values| values.iter().product()),
+        "percentile" => {
+            let percentile = expression.number_arg(1).clamp(0.0, 100.0);
+            series = aggregate(expression, series, |values| {
+                let mut values = values.to_vec();
+                values.sort_by(f64::total_cmp);
+                let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+                values[rank.clamp(1, values.len()) - 1]
+            });
+        }
+        "alias" => {
+            for s in &mut series {
+                s.name = expression.string_arg(1).to_owned();
+            }
+        }
+        "scale" | "offset" => {
+            let number = expression.number_arg(1);
+            let scale = name == "scale";
+            series = series
+                .into_iter()
+                .map(|s| {
+                    let name = format!("{}({}, {})", name, s.name, number);
+                    s.map_values(name, |value| {
+                        if scale {
+                            value * number
+                        } else {
+                            value + number
+                        }
+                    })
+                })
+                .collect();
+        }
+        "diff" => {
+            for s in &mut series {
+                s.name = format!("diff({})", s.name);
+                s.values = s
+                    .values
+                    .windows(2)
+                    .map(|window| MetricValue {
+                        time: window[1].time,
+                        value: window[1].value - window[0].value,
+                    })
+                    .collect();
+            }
+        }
+        "timeShift" => {
+            for s in &mut series {
+                s.name = format!("timeShift({}, {:?})", s.name, expression.string_arg(1));
+                for metric_value in &mut s.values {
+                    metric_value.time = expression.shift_time(1, metric_value.time, false)?;
+                }
+            }
+        }
+        "movingAverage" => {
+            let duration = expression.duration_arg(1)?;
+            for s in &mut series {
+                s.name = format!("movingAverage({}, {:?})", s.name, expression.string_arg(1));
+                // Slides the window of the values sorted by time, including the current value.
+                let values = std::mem::take(&mut s.values);
+                let (mut start, mut sum) = (0, 0.0);
+                for (i, metric_value) in values.iter().enumerate() {
+                    sum += metric_value.value;
+                    let window_start = metric_value.time.checked_sub_signed(duration);
+                    while start < i && window_start.is_some_and(|time| values[start].time <= time) {
+                        sum -= values[start].value;
+                        start += 1;
+                    }
+                    s.values.push(MetricValue {
+                        time: metric_value.time,
+                        value: sum / (i + 1 - start) as f64,
+                    });
+                }
+            }
+        }
+        "linearRegression" => {
+            let start = expression.shift_time(1, to, true)?;
+            for s in &mut series {
+                s.name = format!(
+                    "linearRegression({}, {:?})",
+                    s.name,
+                    expression.string_arg(1)
+                );
+                let points = s
+                    .values
+                    .iter()
+                    .filter(|v| v.time >= start)
+                    .map(|v| (v.time.timestamp() as f64, v.value))
+                    .collect::<Vec<_>>();
+                let Some((slope, intercept)) = linear_regression(&points) else {
+                    s.values.clear();
+                    continue;
+                };
+                s.values.retain(|v| v.time >= from);
+                for metric_value in &mut s.values {
+                    metric_value.value = slope * metric_value.time.timestamp() as f64 + intercept;
+                }
+            }
+        }
+        "divide" => {
+            let divisor = series_args
+                .next()
+                .and_then(|divisors| divisors.into_iter().next())
+                .unwrap_or_else(|| TimeSeries::new(expression.args()[1].to_string(), Vec::new()));
+            let divisors = divisor
+                .values
+                .into_iter()
+                .map(|v| (v.time, v.value))
+                .collect::<BTreeMap<_, _>>();
+            for s in &mut series {
+                s.name = format!("divide({}, {})", s.name, divisor.name);
+                s.values = s
+                    .values
+                    .iter()
+                    .filter_map(|v| {
+                        let divisor = divisors.get(&v.time).filter(|&&divisor| divisor != 0.0)?;
+                        Some(MetricValue {
+                            time: v.time,
+                            value: v.value / divisor,
+                        })
+                    })
+                    .collect();
+            }
+        }
+        _ => unreachable!("validated function {}", name),
+    }
+    Ok(series)
+}
+
+/// Aggregates the values of the series at each time into a series named after the expression.
+fn aggregate(
+    expression: &Expression,
+    series: Vec<TimeSeries>,
+    f: impl Fn(&[f64]) -> f64,
+) -> Vec<TimeSeries> {
+    if series.is_empty() {
+        return Vec::new();
+    }
+    let mut values_by_time = BTreeMap::<DateTime<Utc>, Vec<f64>>::new();
+    for metric_value in series.into_iter().flat_map(|s| s.values) {
+        values_by_time
+            .entry(metric_value.time)
+            .or_default()
+            .push(metric_value.value);
+    }
+    let values = values_by_time
+        .into_iter()
+        .map(|(time, values)| MetricValue {
+            time,
+            value: f(&values),
+        })
+        .collect();
+    vec![TimeSeries::new(expression.to_string(), values)]
+}
+
+/// Returns the slope and the intercept fitted by the least squares method.
+fn linear_regression(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let variance = points
+        .iter()
+        .map(|&(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    if points.len() < 2 || variance == 0.0 {
+        return None;
+    }
+    let covariance = points
+        .iter()
+        .map(|&(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let slope = covariance / variance;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::expression::*;
+
+    #[test]
+    fn parse_expression() {
+        let expression = Expression::parse(
+            " scale( timeShift(host('host0', \"custom.foo.*\"), '1d'), -1.5e3 ) ",
+        )
+        .unwrap();
+        assert_eq!(
+            expression,
+            Expression::call(
+                "scale",
+                [
+                    Expression::call(
+                        "timeShift",
+                        [
+                            Expression::call(
+                                "host",
+                                [
+                                    Expression::string("host0"),
+                                    Expression::string("custom.foo.*"),
+                                ],
+                            ),
+                            Expression::string("1d"),
+                        ],
+                    ),
+                    Expression::number(-1500.0),
+                ],
+            ),
+        );
+        assert_eq!(expression.span, 1..64);
+        let ExpressionKind::Call { ref args, .. } = expression.kind else {
+            panic!("expected a function call");
+        };
+        assert_eq!(args[0].span, 8..54);
+        assert_eq!(args[1].span, 56..62);
+    }
+
+    #[rstest]
+    #[case(
+        "avg(roleSlots('service:role', 'loadavg5'))",
+        "avg(roleSlots(\"service:role\", \"loadavg5\"))"
+    )]
+    #[case(
+        "group( host('host0','loadavg5') , )",
+        "group(host(\"host0\", \"loadavg5\"))"
+    )]
+    #[case(
+        "alias(service(\"service\", \"metric\"), 'it\\'s \"ok\"')",
+        "alias(service(\"service\", \"metric\"), \"it's \\\"ok\\\"\")"
+    )]
+    #[case(
+        "scale(diff(host('host0', 'loadavg5')), 0.000001)",
+        "scale(diff(host(\"host0\", \"loadavg5\")), 0.000001)"
+    )]
+    fn format_expression(#[case] source: &str, #[case] formatted: &str) {
+        let expression = Expression::parse(source).unwrap();
+        assert_eq!(expression.to_string(), formatted);
+        assert_eq!(Expression::parse(formatted), Ok(expression));
+    }
+
+    #[test]
+    fn format_expression_alternate() {
+        let expression = Expression::parse(
+            "group(scale(host('host0', 'loadavg5'), 2), host('host1', 'loadavg5'))",
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{:#}", expression),
+            "group(
+  scale(
+    host(\"host0\", \"loadavg5\"),
+    2
+  ),
+  host(\"host1\", \"loadavg5\")
+)",
+        );
+        assert_eq!(
+            Expression::parse(&format!("{:#}", expression)),
+            Ok(expression)
+        );
+    }
+
+    #[rstest]
+    #[case("", "unexpected end of expression at position 0")]
+    #[case("avg(", "unexpected end of expression at position 4")]
+    #[case("avg(host('host0', 'loadavg5')", "expected , or ) at position 29")]
+    #[case("avg host('host0', 'loadavg5')", "expected ( after avg at position 4")]
+    #[case("avg(host('host0, 'loadavg5'))", "expected , or ) at position 18")]
+    #[case("avg(host('host0', 'loadavg5))", "unterminated string at position 18")]
+    #[case(
+        "scale(host('host0', 'loadavg5'), 1.2.3)",
+        "invalid number 1.2.3 at position 33"
+    )]
+    #[case(
+        "avg(host('host0', 'loadavg5')))",
+        "unexpected trailing input at position 30"
+    )]
+    #[case("avg(@)", "unexpected character @ at position 4")]
+    fn parse_expression_error(#[case] source: &str, #[case] message: &str) {
+        assert_eq!(
+            Expression::parse(source).map_err(|err| err.to_string()),
+            Err(message.to_owned()),
+        );
+    }
+
+    #[rstest]
+    #[case("host('host0', 'loadavg5')")]
+    #[case("avg(roleSlots('service:role', 'loadavg5'))")]
+    #[case("group(host('host0', 'loadavg5'), service('service', 'custom.*'), role('service:role', 'cpu.user.percentage'))")]
+    #[case(
+        "divide(sum(role('service:role', 'custom.foo')), sum(role('service:role', 'custom.bar')))"
+    )]
+    #[case(
+        "alias(percentile(timeShift(stack(role('service:role', 'loadavg5')), '1w'), 95), 'p95')"
+    )]
+    fn validate_expression(#[case] source: &str) {
+        assert_eq!(Expression::parse(source).unwrap().validate(), Ok(()));
+    }
+
+    #[rstest]
+    #[case("avg(host('host0', 'loadavg5'))", vec![])]
+    #[case("avgs(host('host0', 'loadavg5'))", vec!["unknown function avgs at position 0"])]
+    #[case(
+        "group(foo(host('host0', 'loadavg5'), 'bar'), bar())",
+        vec!["unknown function foo at position 6", "unknown function bar at position 45"]
+    )]
+    #[case("diff(host('host0', 'loadavg5'), 'foo', 1)", vec![])]
+    fn expression_warnings(#[case] source: &str, #[case] messages: Vec<&str>) {
+        let expression = Expression::parse(source).unwrap();
+        assert_eq!(expression.validate(), Ok(()));
+        assert_eq!(
+            expression
+                .warnings()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            messages,
+        );
+    }
+
+    #[rstest]
+    #[case("'loadavg5'", vec!["expected a function call at position 0"])]
+    #[case("avg(host('host0', 'loadavg5'), 1)", vec!["avg expects 1 arguments, but got 2 at position 0"])]
+    #[case("group()", vec!["group expects at least 1 arguments, but got 0 at position 0"])]
+    #[case("linearRegression(host('host0', 'loadavg5'))", vec!["linearRegression expects at least 2 arguments, but got 1 at position 0"])]
+    #[case("foo(host('host0', 1))", vec!["expected a string of metric name at position 18"])]
+    #[case("diff(host('host0', 'loadavg5'), host('host0'))", vec!["host expects 2 arguments, but got 1 at position 32"])]
+    #[case(
+        "group(role('service', 'loadavg5'), service('service:role', ''), host('host0', 1))",
+        vec![
+            "invalid role fullname \"service\" at position 11",
+            "invalid service name \"service:role\" at position 43",
+            "invalid metric name \"\" at position 59",
+            "expected a string of metric name at position 78",
+        ]
+    )]
+    #[case(
+        "scale(timeShift(host('host0', 'loadavg5'), '1y'), '2')",
+        vec!["invalid duration \"1y\" at position 43", "expected a number at position 50"]
+    )]
+    #[case(
+        "timeShift(host('host0', 'loadavg5'), '9999999999999w')",
+        vec!["invalid duration \"9999999999999w\" at position 37"]
+    )]
+    fn validate_expression_error(#[case] source: &str, #[case] messages: Vec<&str>) {
+        assert_eq!(
+            Expression::parse(source)
+                .unwrap()
+                .validate()
+                .map_err(|errors| errors.iter().map(ToString::to_string).collect::<Vec<_>>()),
+            Err(messages.into_iter().map(str::to_owned).collect()),
+        );
+    }
+
+    #[rstest]
+    #[case("1m", true)]
+    #[case("30m", true)]
+    #[case("24h", true)]
+    #[case("1d", true)]
+    #[case("1w", true)]
+    #[case("1", false)]
+    #[case("d", false)]
+    #[case("1dd", false)]
+    #[case("-1d", false)]
+    #[case("1000000000w", true)]
+    #[case("9999999999999w", false)]
+    #[case("99999999999999999999m", false)]
+    fn test_is_duration(#[case] s: &str, #[case] expected: bool) {
+        assert_eq!(is_duration(s), expected);
+    }
+
+    fn series(name: &str, values: &[(i64, f64)]) -> TimeSeries {
+        TimeSeries {
+            name: name.to_owned(),
+            values: values
+                .iter()
+                .map(|&(time, value)| MetricValue {
+                    time: DateTime::from_timestamp(time, 0).unwrap(),
+                    value,
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "avg(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 2.0)]), series("b", &[(60, 3.0)])]],
+        vec![series("avg(host(\"host0\", \"loadavg5\"))", &[(60, 2.0), (120, 2.0)])]
+    )]
+    #[case(
+        "max(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 2.0)]), series("b", &[(60, 3.0)])]],
+        vec![series("max(host(\"host0\", \"loadavg5\"))", &[(60, 3.0), (120, 2.0)])]
+    )]
+    #[case(
+        "min(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 2.0)]), series("b", &[(60, 3.0)])]],
+        vec![series("min(host(\"host0\", \"loadavg5\"))", &[(60, 1.0), (120, 2.0)])]
+    )]
+    #[case(
+        "sum(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 2.0)]), series("b", &[(60, 3.0)])]],
+        vec![series("sum(host(\"host0\", \"loadavg5\"))", &[(60, 4.0), (120, 2.0)])]
+    )]
+    #[case("sum(host('host0', 'loadavg5'))", vec![vec![]], vec![])]
+    #[case(
+        "percentile(host('host0', 'loadavg5'), 50)",
+        vec![vec![series("a", &[(60, 4.0)]), series("b", &[(60, 1.0)]), series("c", &[(60, 3.0)])]],
+        vec![series("percentile(host(\"host0\", \"loadavg5\"), 50)", &[(60, 3.0)])]
+    )]
+    #[case(
+        "product(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 2.0), (120, 3.0)]), series("b", &[(60, 4.0)])]],
+        vec![series("product(host(\"host0\", \"loadavg5\"))", &[(60, 8.0), (120, 3.0)])]
+    )]
+    #[case(
+        "group(host('host0', 'a'), host('host0', 'b'))",
+        vec![vec![series("a", &[(60, 1.0)])], vec![series("b", &[(60, 2.0)])]],
+        vec![series("a", &[(60, 1.0)]), series("b", &[(60, 2.0)])]
+    )]
+    #[case(
+        "alias(host('host0', 'loadavg5'), 'load')",
+        vec![vec![series("a", &[(60, 1.0)])]],
+        vec![series("load", &[(60, 1.0)])]
+    )]
+    #[case(
+        "scale(host('host0', 'loadavg5'), 2)",
+        vec![vec![series("a", &[(60, 1.0), (120, 1.5)])]],
+        vec![series("scale(a, 2)", &[(60, 2.0), (120, 3.0)])]
+    )]
+    #[case(
+        "offset(host('host0', 'loadavg5'), -1)",
+        vec![vec![series("a", &[(60, 1.0), (120, 1.5)])]],
+        vec![series("offset(a, -1)", &[(60, 0.0), (120, 0.5)])]
+    )]
+    #[case(
+        "diff(host('host0', 'loadavg5'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 3.0), (180, 2.0)])]],
+        vec![series("diff(a)", &[(120, 2.0), (180, -1.0)])]
+    )]
+    #[case(
+        "timeShift(host('host0', 'loadavg5'), '1h')",
+        vec![vec![series("a", &[(60, 1.0)])]],
+        vec![series("timeShift(a, \"1h\")", &[(3660, 1.0)])]
+    )]
+    #[case(
+        "movingAverage(host('host0', 'loadavg5'), '2m')",
+        vec![vec![series("a", &[(60, 1.0), (120, 3.0), (180, 2.0)])]],
+        vec![series("movingAverage(a, \"2m\")", &[(60, 1.0), (120, 2.0), (180, 2.5)])]
+    )]
+    #[case(
+        "movingAverage(host('host0', 'loadavg5'), '2m')",
+        vec![vec![series("a", &[(60, 1.0), (120, 3.0), (300, 2.0), (360, 4.0), (420, 0.0)])]],
+        vec![series("movingAverage(a, \"2m\")", &[(60, 1.0), (120, 2.0), (300, 2.0), (360, 3.0), (420, 2.0)])]
+    )]
+    #[case(
+        "divide(host('host0', 'a'), host('host0', 'b'))",
+        vec![vec![series("a", &[(60, 1.0)])], vec![]],
+        vec![series("divide(a, host(\"host0\", \"b\"))", &[])]
+    )]
+    #[case(
+        "linearRegression(host('host0', 'loadavg5'), '2m')",
+        vec![vec![series("a", &[(0, 10.0), (60, 1.0), (120, 2.0), (180, 3.0)])]],
+        vec![series("linearRegression(a, \"2m\")", &[(60, 1.0), (120, 2.0), (180, 3.0)])]
+    )]
+    #[case(
+        "divide(host('host0', 'a'), host('host0', 'b'))",
+        vec![vec![series("a", &[(60, 1.0), (120, 3.0), (180, 2.0)])], vec![series("b", &[(60, 2.0), (120, 0.0)])]],
+        vec![series("divide(a, b)", &[(60, 0.5)])]
+    )]
+    fn test_apply_function(
+        #[case] source: &str,
+        #[case] series_args: Vec<Vec<TimeSeries>>,
+        #[case] expected: Vec<TimeSeries>,
+    ) {
+        assert_eq!(
+            apply_function(
+                &Expression::parse(source).unwrap(),
+                series_args,
+                DateTime::from_timestamp(60, 0).unwrap(),
+                DateTime::from_timestamp(180, 0).unwrap(),
+            ),
+            Ok(expected),
+        );
+    }
+
+    #[rstest]
+    #[case("timeShift(host('host0', 'loadavg5'), '1000000000w')")]
+    #[case("linearRegression(host('host0', 'loadavg5'), '1000000000w')")]
+    fn test_apply_function_duration_out_of_range(#[case] source: &str) {
+        assert_eq!(
+            apply_function(
+                &Expression::parse(source).unwrap(),
+                vec![vec![series("a", &[(60, 1.0)])]],
+                DateTime::from_timestamp(60, 0).unwrap(),
+                DateTime::from_timestamp(180, 0).unwrap(),
+            ),
+            Err(ExpressionError::new(
+                "invalid duration \"1000000000w\"",
+                source.rfind("'1").unwrap(),
+            )),
+        );
+    }
+
+    #[rstest]
+    #[case("avg(host('host0', 'loadavg5'))", Ok(()))]
+    #[case("diff(host('host0', 'loadavg5'))", Ok(()))]
+    #[case(
+        "group(avgs(host('host0', 'loadavg5')))",
+        Err("unknown function avgs is not supported by the evaluation at position 6")
+    )]
+    #[case(
+        "diff(host('host0', 'loadavg5'), 1)",
+        Err("diff with 2 arguments is not supported by the evaluation at position 0")
+    )]
+    fn test_check_evaluable(#[case] source: &str, #[case] expected: std::result::Result<(), &str>) {
+        assert_eq!(
+            check_evaluable(&Expression::parse(source).unwrap()).map_err(|err| err.to_string()),
+            expected.map_err(str::to_owned),
+        );
+    }
+
+    #[rstest]
+    #[case("30m", TimeDelta::minutes(30))]
+    #[case("24h", TimeDelta::hours(24))]
+    #[case("1d", TimeDelta::days(1))]
+    #[case("2w", TimeDelta::weeks(2))]
+    fn test_parse_duration(#[case] s: &str, #[case] expected: TimeDelta) {
+        assert_eq!(parse_duration(s), Some(expected));
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod client_tests {
+    use httptest::{all_of, matchers::*, responders::*, Expectation};
+    use serde_json::json;
+
+    use crate::error::Error;
+    use crate::expression::*;
+    use crate::test_util::fixtures;
+    use crate::tests::*;
+
+    fn metric_values(values: &[(i64, f64)]) -> Vec<MetricValue> {
+        values
+            .iter()
+            .map(|&(time, value)| MetricValue {
+                time: DateTime::from_timestamp(time, 0).unwrap(),
+                value,
+            })
+            .collect()
+    }
+
+    #[async_std::test]
+    async fn evaluate_expression() {
+        let mut host2 = fixtures::host();
+        host2.id = "host2".into();
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/hosts",
+            query_params = "service=service1&role=role1",
+            response = json!({ "hosts": [fixtures::host(), host2] }),
+        };
+        for (host_id, metrics) in [
+            (
+                "host1",
+                json!([{ "time": 1699999860, "value": 1.0 }, { "time": 1699999920, "value": 2.0 }]),
+            ),
+            ("host2", json!([{ "time": 1699999860, "value": 3.0 }])),
+        ] {
+            server.expect(
+                Expectation::matching(all_of![
+                    request::method_path("GET", format!("/api/v0/hosts/{}/metrics", host_id)),
+                    request::query(url_decoded(all_of![
+                        contains(("name", "loadavg5")),
+                        contains(("from", "1699999860")),
+                        contains(("to", "1700000000")),
+                    ])),
+                ])
+                .respond_with(json_encoded(json!({ "metrics": metrics }))),
+            );
+        }
+        let client = test_client!(server);
+        let from = DateTime::from_timestamp(1699999860, 0).unwrap();
+        let to = DateTime::from_timestamp(1700000000, 0).unwrap();
+        assert_eq!(
+            client
+                .evaluate_expression(
+                    Expression::parse("alias(avg(role('service1:role1', 'loadavg5')), 'avg')")
+                        .unwrap(),
+                    from,
+                    to,
+                )
+                .await,
+            Ok(vec![TimeSeries {
+                name: "avg".to_owned(),
+                values: metric_values(&[(1699999860, 2.0), (1699999920, 2.0)]),
+            }]),
+        );
+        assert_eq!(
+            client
+                .evaluate_expression(Expression::parse("avg(host('host0'))").unwrap(), from, to)
+                .await,
+            Err(Error::InvalidExpression(ExpressionError {
+                message: "host expects 2 arguments, but got 1".to_owned(),
+                position: 4,
+            })),
+        );
+        assert_eq!(
+            client
+                .evaluate_expression(
+                    Expression::parse("timeShift(host('host0', 'loadavg5'), '1000000000w')")
+                        .unwrap(),
+                    from,
+                    to,
+                )
+                .await,
+            Err(Error::InvalidExpression(ExpressionError {
+                message: "invalid duration \"1000000000w\"".to_owned(),
+                position: 37,
+            })),
+        );
+    }
+
+    #[async_std::test]
+    async fn evaluate_expression_wildcard() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/services/service1/metric-names",
+            response = json!({ "names": ["custom.foo", "custom.bar", "other"] }),
+        };
+        for (metric_name, value) in [("custom.foo", 1.0), ("custom.bar", 2.0)] {
+            server.expect(
+                Expectation::matching(all_of![
+                    request::method_path("GET", "/api/v0/services/service1/metrics"),
+                    request::query(url_decoded(all_of![
+                        contains(("name", metric_name)),
+                        contains(("from", "1699996260")),
+                        contains(("to", "1699996400")),
+                    ])),
+                ])
+                .respond_with(json_encoded(json!({
+                    "metrics": [{ "time": 1699996260, "value": value }],
+                }))),
+            );
+        }
+        assert_eq!(
+            test_client!(server)
+                .evaluate_expression(
+                    Expression::parse("timeShift(service('service1', 'custom.*'), '1h')").unwrap(),
+                    DateTime::from_timestamp(1699999860, 0).unwrap(),
+                    DateTime::from_timestamp(1700000000, 0).unwrap(),
+                )
+                .await,
+            Ok(vec![
+                TimeSeries {
+                    name: "timeShift(service(\"service1\", \"custom.bar\"), \"1h\")".to_owned(),
+                    values: metric_values(&[(1699999860, 2.0)]),
+                },
+                TimeSeries {
+                    name: "timeShift(service(\"service1\", \"custom.foo\"), \"1h\")".to_owned(),
+                    values: metric_values(&[(1699999860, 1.0)]),
+                },
+            ]),
+        );
+    }
+}
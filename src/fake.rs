@@ -0,0 +1,951 @@
+//! In-process fake Mackerel server for end-to-end tests
+//!
+//! [`FakeServer`] implements a stateful in-memory Mackerel API.
+//! The hosts, services, roles, monitors, alerts, downtimes, dashboards, metadata and metrics
+//! persist across the requests, and the ids are generated in the same format as [`Id`](crate::entity::Id).
+//! ```rust
+//! use mackerel_client::fake::FakeServer;
+//! use mackerel_client::host::HostValue;
+//! use mackerel_client::Client;
+//!
+//! # #[async_std::main]
+//! # async fn main() -> mackerel_client::error::Result<()> {
+//! let fake = FakeServer::new();
+//! let client = Client::builder()
+//!     .api_key("<Mackerel-API-KEY>")
+//!     .api_base(fake.url())
+//!     .build();
+//!
+//! let host_id = client
+//!     .create_host(HostValue::builder().name("example-host").build())
+//!     .await?;
+//! assert_eq!(client.get_host(host_id).await?.name, "example-host");
+//! assert_eq!(fake.state().hosts.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+use chrono::{DateTime, Utc};
+use http::{Method, StatusCode};
+use httptest::bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::alert::{Alert, AlertId, AlertLog, AlertLogTrigger, AlertLogValue, AlertStatus};
+use crate::dashboard::{Dashboard, DashboardValue};
+use crate::downtime::{Downtime, DowntimeValue};
+use crate::entity::Entity;
+use crate::host::{Host, HostId, HostSize, HostStatus, HostValue};
+use crate::metric::{HostMetricValue, MetricValue, ServiceMetricValue};
+use crate::monitor::{Monitor, MonitorValue};
+use crate::role::{Role, RoleFullname, RoleName};
+use crate::service::{Service, ServiceName};
+
+/// A fake Mackerel server running in the current process.
+/// The server stops on drop.
+pub struct FakeServer {
+    server: httptest::Server,
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl std::fmt::Debug for FakeServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeServer")
+            .field("addr", &self.server.addr())
+            .finish()
+    }
+}
+
+impl Default for FakeServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeServer {
+    /// Starts a new fake server with the empty state.
+    pub fn new() -> Self {
+        Self::with_state(FakeState::default())
+    }
+
+    /// Starts a new fake server with the state.
+    pub fn with_state(state: FakeState) -> Self {
+        let state = Arc::new(Mutex::new(state));
+        let server = httptest::Server::run();
+        server.expect(
+            httptest::Expectation::matching(httptest::matchers::any())
+                .times(..)
+                .respond_with(FakeResponder(state.clone())),
+        );
+        Self { server, state }
+    }
+
+    /// Returns the url of the server, to be configured as the API base.
+    pub fn url(&self) -> String {
+        self.server.url_str("/")
+    }
+
+    /// Locks and returns the state of the server, to seed or inspect the entities.
+    ///
+    /// The server locks the state on each request, so drop the guard before calling the client;
+    /// holding it across a client call deadlocks.
+    pub fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap()
+    }
+}
+
+/// The state of [`FakeServer`].
+#[derive(Clone, Debug, Default)]
+pub struct FakeState {
+    pub hosts: Vec<Host>,
+    pub services: Vec<Service>,
+    pub roles: HashMap<ServiceName, Vec<Role>>,
+    pub monitors: Vec<Monitor>,
+    pub alerts: Vec<Alert>,
+    /// The status transition logs of the alerts, appended on closing the alerts.
+    pub alert_logs: HashMap<AlertId, Vec<AlertLog>>,
+    pub downtimes: Vec<Downtime>,
+    pub dashboards: Vec<Dashboard>,
+    /// The metadata keyed by the target path (`hosts/<id>`, `services/<name>`
+    /// or `services/<name>/roles/<name>`) and the namespace.
+    pub metadata: BTreeMap<(String, String), Value>,
+    pub host_metrics: Vec<HostMetricValue>,
+    pub service_metrics: HashMap<ServiceName, Vec<ServiceMetricValue>>,
+}
+
+/// Generates a new id in the same format as the ids of Mackerel.
+pub fn generate_id<T>() -> crate::entity::Id<T> {
+    const CHARS: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    (0..11)
+        .map(|_| CHARS[fastrand::usize(..CHARS.len())] as char)
+        .collect::<String>()
+        .into()
+}
+
+struct FakeResponder(Arc<Mutex<FakeState>>);
+
+type FakeResult = std::result::Result<Value, (StatusCode, String)>;
+
+impl httptest::responders::Responder for FakeResponder {
+    fn respond<'a>(
+        &mut self,
+        request: &'a http::Request<Bytes>,
+    ) -> Pin<Box<dyn Future<Output = http::Response<Bytes>> + Send + 'a>> {
+        let (status, body) = match self.0.lock().unwrap().handle(request) {
+            Ok(body) => (StatusCode::OK, body),
+            Err((status, message)) => (status, json!({ "error": { "message": message } })),
+        };
+        let response = http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Bytes::from(body.to_string()))
+            .unwrap();
+        Box::pin(std::future::ready(response))
+    }
+}
+
+fn not_found(message: impl Into<String>) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, message.into())
+}
+
+fn bad_request(message: impl Into<String>) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, message.into())
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> std::result::Result<T, (StatusCode, String)> {
+    serde_json::from_slice(body).map_err(|err| bad_request(err.to_string()))
+}
+
+fn to_value(value: impl serde::Serialize) -> FakeResult {
+    Ok(serde_json::to_value(value).unwrap())
+}
+
+fn timestamp(value: Option<&str>) -> std::result::Result<DateTime<Utc>, (StatusCode, String)> {
+    value
+        .and_then(|value| value.parse().ok())
+        .and_then(|value| DateTime::from_timestamp(value, 0))
+        .ok_or_else(|| bad_request("invalid from or to parameter"))
+}
+
+fn find<'a, T>(
+    entities: &'a mut [Entity<T>],
+    id: &str,
+) -> std::result::Result<&'a mut Entity<T>, (StatusCode, String)> {
+    entities
+        .iter_mut()
+        .find(|entity| &*entity.id == id)
+        .ok_or_else(|| not_found(format!("{} not found", id)))
+}
+
+/// A page of the entities and the id of the next page.
+type Page<'a, T> = (Vec<&'a Entity<T>>, Option<crate::entity::Id<T>>);
+
+fn paginate<'a, T>(
+    mut entities: Vec<&'a Entity<T>>,
+    next_id: Option<&str>,
+    limit: Option<&str>,
+) -> std::result::Result<Page<'a, T>, (StatusCode, String)> {
+    let limit = limit
+        .and_then(|limit| limit.parse::<usize>().ok())
+        .unwrap_or(100);
+    if let Some(next_id) = next_id {
+        let index = entities
+            .iter()
+            .position(|entity| &*entity.id == next_id)
+            .ok_or_else(|| bad_request("invalid nextId"))?;
+        entities.drain(..index);
+    }
+    let next_id = entities.get(limit).map(|entity| entity.id);
+    entities.truncate(limit);
+    Ok((entities, next_id))
+}
+
+fn remove<T>(
+    entities: &mut Vec<Entity<T>>,
+    id: &str,
+) -> std::result::Result<Entity<T>, (StatusCode, String)> {
+    let index = entities
+        .iter()
+        .position(|entity| &*entity.id == id)
+        .ok_or_else(|| not_found(format!("{} not found", id)))?;
+    Ok(entities.remove(index))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HostStatusesRequest {
+    #[serde(default)]
+    ids: Vec<HostId>,
+    status: Option<HostStatus>,
+}
+
+impl FakeState {
+    fn handle(&mut self, request: &http::Request<Bytes>) -> FakeResult {
+        let method = request.method().clone();
+        let path = request.uri().path().trim_start_matches("/api/v0/");
+        let segments = path.split('/').collect::<Vec<_>>();
+        let query =
+            url::form_urlencoded::parse(request.uri().query().unwrap_or_default().as_bytes())
+                .into_owned()
+                .collect::<Vec<_>>();
+        let query_values = |key: &str| {
+            query
+                .iter()
+                .filter(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+                .collect::<Vec<_>>()
+        };
+        let query_value = |key: &str| query_values(key).first().copied();
+        let body = request.body();
+        match (&method, segments.as_slice()) {
+            (&Method::GET, ["hosts"]) => {
+                let statuses = query_values("status");
+                let hosts = self
+                    .hosts
+                    .iter()
+                    .filter(|host| !host.is_retired)
+                    .filter(|host| {
+                        query_value("service").is_none_or(|service_name| {
+                            host.roles.iter().any(|(name, role_names)| {
+                                **name == *service_name
+                                    && (query_values("role").is_empty()
+                                        || role_names.iter().any(|role_name| {
+                                            query_values("role").contains(&&**role_name)
+                                        }))
+                            })
+                        })
+                    })
+                    .filter(|host| query_value("name").is_none_or(|name| host.name == name))
+                    .filter(|host| {
+                        let status = host.status.to_string();
+                        if statuses.is_empty() {
+                            matches!(host.status, HostStatus::Working | HostStatus::Standby)
+                        } else {
+                            statuses.contains(&status.as_str())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Ok(json!({ "hosts": hosts }))
+            }
+            (&Method::POST, ["hosts"]) => {
+                let host_value = parse::<HostValue>(body)?;
+                self.validate_role_fullnames(&host_value.role_fullnames)?;
+                let host = Host {
+                    id: generate_id(),
+                    created_at: Utc::now(),
+                    size: HostSize::Standard,
+                    status: HostStatus::Working,
+                    is_retired: false,
+                    retired_at: None,
+                    roles: host_roles(&host_value.role_fullnames),
+                    value: host_value,
+                };
+                let id = host.id;
+                self.hosts.push(host);
+                Ok(json!({ "id": id }))
+            }
+            (&Method::POST, ["hosts", "bulk-update-statuses"]) => {
+                let request = parse::<HostStatusesRequest>(body)?;
+                let status = request
+                    .status
+                    .ok_or_else(|| bad_request("status is required"))?;
+                for id in request.ids {
+                    self.find_host(&id)?.status = status;
+                }
+                Ok(json!({ "success": true }))
+            }
+            (&Method::POST, ["hosts", "bulk-retire"]) => {
+                let request = parse::<HostStatusesRequest>(body)?;
+                for id in request.ids {
+                    self.retire_host(&id)?;
+                }
+                Ok(json!({ "success": true }))
+            }
+            (&Method::GET, ["hosts-by-custom-identifier", custom_identifier]) => {
+                let host = self.hosts.iter().find(|host| {
+                    !host.is_retired
+                        && host.custom_identifier.as_deref() == Some(*custom_identifier)
+                });
+                Ok(json!({ "host": host }))
+            }
+            (&Method::GET, ["hosts", id]) => Ok(json!({ "host": self.find_host(id)? })),
+            (&Method::PUT, ["hosts", id]) => {
+                let host_value = parse::<HostValue>(body)?;
+                self.validate_role_fullnames(&host_value.role_fullnames)?;
+                let host = self.find_host(id)?;
+                host.roles = host_roles(&host_value.role_fullnames);
+                host.value = host_value;
+                Ok(json!({ "id": host.id }))
+            }
+            (&Method::POST, ["hosts", id, "status"]) => {
+                let request = parse::<HostStatusesRequest>(body)?;
+                let status = request
+                    .status
+                    .ok_or_else(|| bad_request("status is required"))?;
+                self.find_host(id)?.status = status;
+                Ok(json!({ "success": true }))
+            }
+            (&Method::PUT, ["hosts", id, "role-fullnames"]) => {
+                #[derive(Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Request {
+                    role_fullnames: Vec<RoleFullname>,
+                }
+                let request = parse::<Request>(body)?;
+                self.validate_role_fullnames(&request.role_fullnames)?;
+                let host = self.find_host(id)?;
+                host.roles = host_roles(&request.role_fullnames);
+                host.value.role_fullnames = request.role_fullnames;
+                Ok(json!({ "success": true }))
+            }
+            (&Method::POST, ["hosts", id, "retire"]) => {
+                self.retire_host(id)?;
+                Ok(json!({ "success": true }))
+            }
+            (&Method::GET, ["hosts", id, "metric-names"]) => {
+                let host_id = self.find_host(id)?.id;
+                let mut names = self
+                    .host_metrics
+                    .iter()
+                    .filter(|metric| metric.host_id == host_id)
+                    .map(|metric| metric.name.clone())
+                    .collect::<Vec<_>>();
+                names.sort();
+                names.dedup();
+                Ok(json!({ "names": names }))
+            }
+            (&Method::GET, ["hosts", id, "monitored-statuses"]) => {
+                self.find_host(id)?;
+                Ok(json!({ "monitoredStatuses": [] }))
+            }
+            (&Method::GET, ["hosts", id, "metrics"]) => {
+                let host_id = self.find_host(id)?.id;
+                let name = query_value("name").unwrap_or_default();
+                let (from, to) = (
+                    timestamp(query_value("from"))?,
+                    timestamp(query_value("to"))?,
+                );
+                let metrics = self
+                    .host_metrics
+                    .iter()
+                    .filter(|metric| metric.host_id == host_id && metric.name == name)
+                    .map(|metric| &metric.value)
+                    .filter(|value| (from..=to).contains(&value.time))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "metrics": metrics }))
+            }
+            (&Method::POST, ["tsdb"]) => {
+                let metrics = parse::<Vec<HostMetricValue>>(body)?;
+                for metric in &metrics {
+                    self.find_host(&metric.host_id)?;
+                }
+                self.host_metrics.extend(metrics);
+                Ok(json!({ "success": true }))
+            }
+            (&Method::GET, ["tsdb", "latest"]) => {
+                let names = query_values("name");
+                let host_ids = query_values("hostId")
+                    .into_iter()
+                    .map(|id| self.find_host(id).map(|host| host.id))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let mut latest = HashMap::<HostId, HashMap<String, Option<&MetricValue>>>::new();
+                for host_id in host_ids {
+                    let values = latest.entry(host_id).or_default();
+                    for name in &names {
+                        let value = self
+                            .host_metrics
+                            .iter()
+                            .filter(|metric| metric.host_id == host_id && metric.name == *name)
+                            .map(|metric| &metric.value)
+                            .max_by_key(|value| value.time);
+                        values.insert(name.to_string(), value);
+                    }
+                }
+                Ok(json!({ "tsdbLatest": latest }))
+            }
+            (&Method::GET, ["hosts", id, "metadata"]) => {
+                self.find_host(id)?;
+                Ok(self.list_metadata(format!("hosts/{}", id)))
+            }
+            (method, ["hosts", id, "metadata", namespace]) => {
+                self.find_host(id)?;
+                self.handle_metadata(method, format!("hosts/{}", id), namespace, body)
+            }
+
+            (&Method::GET, ["services"]) => {
+                let services = self
+                    .services
+                    .iter()
+                    .map(|service| self.service_with_roles(service))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "services": services }))
+            }
+            (&Method::POST, ["services"]) => {
+                let service = parse::<Service>(body)?;
+                if self.services.iter().any(|s| s.name == service.name) {
+                    return Err(bad_request(format!(
+                        "service {} already exists",
+                        service.name
+                    )));
+                }
+                self.services.push(Service {
+                    roles: Vec::new(),
+                    ..service.clone()
+                });
+                to_value(self.service_with_roles(&service))
+            }
+            (&Method::DELETE, ["services", name]) => {
+                let index = self.service_index(name)?;
+                let service = self.service_with_roles(&self.services[index]);
+                self.services.remove(index);
+                self.roles.remove(&service.name);
+                to_value(service)
+            }
+            (&Method::GET, ["services", name, "metric-names"]) => {
+                let service_name = self.services[self.service_index(name)?].name;
+                let mut names = self
+                    .service_metrics
+                    .get(&service_name)
+                    .into_iter()
+                    .flatten()
+                    .map(|metric| metric.name.clone())
+                    .collect::<Vec<_>>();
+                names.sort();
+                names.dedup();
+                Ok(json!({ "names": names }))
+            }
+            (&Method::POST, ["services", name, "tsdb"]) => {
+                let service_name = self.services[self.service_index(name)?].name;
+                let metrics = parse::<Vec<ServiceMetricValue>>(body)?;
+                self.service_metrics
+                    .entry(service_name)
+                    .or_default()
+                    .extend(metrics);
+                Ok(json!({ "success": true }))
+            }
+            (&Method::GET, ["services", name, "metrics"]) => {
+                let service_name = self.services[self.service_index(name)?].name;
+                let metric_name = query_value("name").unwrap_or_default();
+                let (from, to) = (
+                    timestamp(query_value("from"))?,
+                    timestamp(query_value("to"))?,
+                );
+                let metrics = self
+                    .service_metrics
+                    .get(&service_name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|metric| metric.name == metric_name)
+                    .map(|metric| &metric.value)
+                    .filter(|value| (from..=to).contains(&value.time))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "metrics": metrics }))
+            }
+            (&Method::GET, ["services", name, "metadata"]) => {
+                self.service_index(name)?;
+                Ok(self.list_metadata(format!("services/{}", name)))
+            }
+            (method, ["services", name, "metadata", namespace]) => {
+                self.service_index(name)?;
+                self.handle_metadata(method, format!("services/{}", name), namespace, body)
+            }
+
+            (&Method::GET, ["services", name, "roles"]) => {
+                let service_name = self.services[self.service_index(name)?].name;
+                let roles = self.roles.get(&service_name).cloned().unwrap_or_default();
+                Ok(json!({ "roles": roles }))
+            }
+            (&Method::POST, ["services", name, "roles"]) => {
+                let service_name = self.services[self.service_index(name)?].name;
+                let role = parse::<Role>(body)?;
+                let roles = self.roles.entry(service_name).or_default();
+                if roles.iter().any(|r| r.name == role.name) {
+                    return Err(bad_request(format!("role {} already exists", role.name)));
+                }
+                roles.push(role.clone());
+                to_value(role)
+            }
+            (&Method::DELETE, ["services", name, "roles", role_name]) => {
+                let index = self.role_index(name, role_name)?;
+                let service_name = self.services[self.service_index(name)?].name;
+                let role = self.roles.get_mut(&service_name).unwrap().remove(index);
+                to_value(role)
+            }
+            (&Method::GET, ["services", name, "roles", role_name, "metadata"]) => {
+                self.role_index(name, role_name)?;
+                Ok(self.list_metadata(format!("services/{}/roles/{}", name, role_name)))
+            }
+            (method, ["services", name, "roles", role_name, "metadata", namespace]) => {
+                self.role_index(name, role_name)?;
+                let target = format!("services/{}/roles/{}", name, role_name);
+                self.handle_metadata(method, target, namespace, body)
+            }
+
+            (&Method::GET, ["monitors"]) => Ok(json!({ "monitors": self.monitors })),
+            (&Method::POST, ["monitors"]) => {
+                let monitor = Monitor::new(generate_id(), parse::<MonitorValue>(body)?);
+                self.monitors.push(monitor.clone());
+                to_value(monitor)
+            }
+            (&Method::GET, ["monitors", id]) => {
+                Ok(json!({ "monitor": find(&mut self.monitors, id)? }))
+            }
+            (&Method::PUT, ["monitors", id]) => {
+                let monitor_value = parse::<MonitorValue>(body)?;
+                let monitor = find(&mut self.monitors, id)?;
+                monitor.value = monitor_value;
+                to_value(monitor)
+            }
+            (&Method::DELETE, ["monitors", id]) => to_value(remove(&mut self.monitors, id)?),
+
+            (&Method::GET, ["alerts"]) => {
+                let with_closed = query_value("withClosed") == Some("true");
+                let mut alerts = self
+                    .alerts
+                    .iter()
+                    .filter(|alert| with_closed || alert.closed_at.is_none())
+                    .collect::<Vec<_>>();
+                alerts.sort_by_key(|alert| std::cmp::Reverse(alert.opened_at));
+                let (alerts, next_id) =
+                    paginate(alerts, query_value("nextId"), query_value("limit"))?;
+                Ok(json!({ "alerts": alerts, "nextId": next_id }))
+            }
+            (&Method::GET, ["alerts", id]) => to_value(find(&mut self.alerts, id)?),
+            (&Method::PUT, ["alerts", id]) => {
+                #[derive(Deserialize)]
+                struct Request {
+                    memo: String,
+                }
+                let request = parse::<Request>(body)?;
+                let alert = find(&mut self.alerts, id)?;
+                alert
+                    .value
+                    .extra
+                    .insert("memo".to_owned(), json!(request.memo));
+                Ok(json!({ "id": alert.id, "memo": request.memo }))
+            }
+            (&Method::POST, ["alerts", id, "close"]) => {
+                #[derive(Deserialize)]
+                struct Request {
+                    reason: String,
+                }
+                let request = parse::<Request>(body)?;
+                let alert = find(&mut self.alerts, id)?;
+                alert.value.status = AlertStatus::Ok;
+                alert.value.reason = Some(request.reason);
+                alert.value.closed_at.get_or_insert_with(Utc::now);
+                let alert = alert.clone();
+                self.alert_logs
+                    .entry(alert.id)
+                    .or_default()
+                    .push(AlertLog::new(
+                        generate_id(),
+                        AlertLogValue::builder()
+                            .status(AlertStatus::Ok)
+                            .trigger(AlertLogTrigger::Manual)
+                            .created_at(Utc::now())
+                            .build(),
+                    ));
+                to_value(alert)
+            }
+            (&Method::GET, ["alerts", id, "logs"]) => {
+                let alert_id = find(&mut self.alerts, id)?.id;
+                let mut logs = self
+                    .alert_logs
+                    .get(&alert_id)
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                logs.sort_by_key(|log| std::cmp::Reverse(log.created_at));
+                let (logs, next_id) = paginate(logs, query_value("nextId"), query_value("limit"))?;
+                Ok(json!({ "logs": logs, "nextId": next_id }))
+            }
+
+            (&Method::GET, ["downtimes"]) => Ok(json!({ "downtimes": self.downtimes })),
+            (&Method::POST, ["downtimes"]) => {
+                let downtime = Downtime::new(generate_id(), parse::<DowntimeValue>(body)?);
+                self.downtimes.push(downtime.clone());
+                to_value(downtime)
+            }
+            (&Method::PUT, ["downtimes", id]) => {
+                let downtime_value = parse::<DowntimeValue>(body)?;
+                let downtime = find(&mut self.downtimes, id)?;
+                downtime.value = downtime_value;
+                to_value(downtime)
+            }
+            (&Method::DELETE, ["downtimes", id]) => to_value(remove(&mut self.downtimes, id)?),
+
+            (&Method::GET, ["dashboards"]) => Ok(json!({ "dashboards": self.dashboards })),
+            (&Method::POST, ["dashboards"]) => {
+                let dashboard = Dashboard::new(generate_id(), parse::<DashboardValue>(body)?);
+                self.dashboards.push(dashboard.clone());
+                to_value(dashboard)
+            }
+            (&Method::GET, ["dashboards", id]) => to_value(find(&mut self.dashboards, id)?),
+            (&Method::PUT, ["dashboards", id]) => {
+                let dashboard_value = parse::<DashboardValue>(body)?;
+                let dashboard = find(&mut self.dashboards, id)?;
+                dashboard.value = dashboard_value;
+                to_value(dashboard)
+            }
+            (&Method::DELETE, ["dashboards", id]) => to_value(remove(&mut self.dashboards, id)?),
+
+            _ => Err(not_found(format!("{} /api/v0/{} not found", method, path))),
+        }
+    }
+
+    fn find_host(&mut self, id: &str) -> std::result::Result<&mut Host, (StatusCode, String)> {
+        self.hosts
+            .iter_mut()
+            .find(|host| &*host.id == id)
+            .ok_or_else(|| not_found("Host Not Found."))
+    }
+
+    fn retire_host(&mut self, id: &str) -> std::result::Result<(), (StatusCode, String)> {
+        let host = self.find_host(id)?;
+        host.is_retired = true;
+        host.retired_at = Some(Utc::now());
+        Ok(())
+    }
+
+    fn service_index(&self, name: &str) -> std::result::Result<usize, (StatusCode, String)> {
+        self.services
+            .iter()
+            .position(|service| *service.name == *name)
+            .ok_or_else(|| not_found("Service not found."))
+    }
+
+    fn role_index(
+        &self,
+        service_name: &str,
+        role_name: &str,
+    ) -> std::result::Result<usize, (StatusCode, String)> {
+        let service_name = self.services[self.service_index(service_name)?].name;
+        self.roles
+            .get(&service_name)
+            .and_then(|roles| roles.iter().position(|role| *role.name == *role_name))
+            .ok_or_else(|| not_found("Role not found."))
+    }
+
+    fn service_with_roles(&self, service: &Service) -> Service {
+        Service {
+            roles: self
+                .roles
+                .get(&service.name)
+                .into_iter()
+                .flatten()
+                .map(|role| role.name)
+                .collect(),
+            ..service.clone()
+        }
+    }
+
+    fn validate_role_fullnames(
+        &self,
+        role_fullnames: &[RoleFullname],
+    ) -> std::result::Result<(), (StatusCode, String)> {
+        for role_fullname in role_fullnames {
+            self.role_index(&role_fullname.service_name, &role_fullname.role_name)?;
+        }
+        Ok(())
+    }
+
+    fn list_metadata(&self, target: String) -> Value {
+        let metadata = self
+            .metadata
+            .keys()
+            .filter(|(t, _)| *t == target)
+            .map(|(_, namespace)| json!({ "namespace": namespace }))
+            .collect::<Vec<_>>();
+        json!({ "metadata": metadata })
+    }
+
+    fn handle_metadata(
+        &mut self,
+        method: &Method,
+        target: String,
+        namespace: &str,
+        body: &[u8],
+    ) -> FakeResult {
+        let key = (target, namespace.to_owned());
+        match *method {
+            Method::GET => self
+                .metadata
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| not_found("Metadata not found.")),
+            Method::PUT => {
+                self.metadata.insert(key, parse::<Value>(body)?);
+                Ok(json!({ "success": true }))
+            }
+            Method::DELETE => self
+                .metadata
+                .remove(&key)
+                .map(|_| json!({ "success": true }))
+                .ok_or_else(|| not_found("Metadata not found.")),
+            _ => Err(not_found(format!("{} not found", method))),
+        }
+    }
+}
+
+fn host_roles(role_fullnames: &[RoleFullname]) -> HashMap<ServiceName, Vec<RoleName>> {
+    let mut roles = HashMap::<ServiceName, Vec<RoleName>>::new();
+    for role_fullname in role_fullnames {
+        roles
+            .entry(role_fullname.service_name)
+            .or_default()
+            .push(role_fullname.role_name);
+    }
+    roles
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+
+    use crate::alert::{AlertId, AlertLogId, AlertValue};
+    use crate::fake::*;
+    use crate::host::ListHostsParams;
+    use crate::monitor::MonitorType;
+
+    fn client(fake: &FakeServer) -> crate::Client {
+        crate::Client::builder()
+            .api_key("<Mackerel-API-KEY>")
+            .api_base(fake.url())
+            .build()
+    }
+
+    #[test]
+    fn test_generate_id() {
+        for _ in 0..100 {
+            let id = generate_id::<HostValue>();
+            assert_eq!(id.len(), 11);
+            assert_eq!(id, id.to_string().parse().unwrap());
+        }
+    }
+
+    #[async_std::test]
+    async fn hosts_and_roles() {
+        let fake = FakeServer::new();
+        let client = client(&fake);
+        client
+            .create_service(Service::builder().name("service0").build())
+            .await
+            .unwrap();
+        client
+            .create_role("service0", Role::builder().name("role0").build())
+            .await
+            .unwrap();
+        assert!(client
+            .create_host(
+                HostValue::builder()
+                    .name("host0")
+                    .role_fullnames(["service0:role1"])
+                    .build()
+            )
+            .await
+            .is_err_and(|err| err.is_not_found()));
+        let host_id = client
+            .create_host(
+                HostValue::builder()
+                    .name("host0")
+                    .role_fullnames(["service0:role0"])
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let other_host_id = client
+            .create_host(HostValue::builder().name("host1").build())
+            .await
+            .unwrap();
+        assert_ne!(host_id, other_host_id);
+        assert_eq!(
+            client
+                .list_hosts(ListHostsParams::role_fullname("service0:role0"))
+                .await
+                .map(|hosts| hosts.into_iter().map(|host| host.id).collect::<Vec<_>>()),
+            Ok(vec![host_id]),
+        );
+        assert_eq!(
+            client.list_services().await,
+            Ok(vec![Service::builder()
+                .name("service0")
+                .roles(["role0"])
+                .build()]),
+        );
+        client
+            .update_host_status(host_id, HostStatus::Maintenance)
+            .await
+            .unwrap();
+        assert_eq!(client.list_hosts(()).await.unwrap().len(), 1);
+        client.retire_host(other_host_id).await.unwrap();
+        assert_eq!(client.list_hosts(()).await, Ok(vec![]));
+        assert_eq!(
+            client.get_host(host_id).await.map(|host| host.status),
+            Ok(HostStatus::Maintenance),
+        );
+
+        client
+            .put_host_metadata(host_id, "namespace0", json!({ "key": "value" }))
+            .await
+            .unwrap();
+        assert_eq!(
+            client.get_host_metadata(host_id, "namespace0").await,
+            Ok(json!({ "key": "value" })),
+        );
+        assert_eq!(client.list_host_metadata(host_id).await.unwrap().len(), 1);
+
+        let time = DateTime::from_timestamp(1700000000, 0).unwrap();
+        client
+            .post_host_metric_values([HostMetricValue::builder()
+                .host_id(host_id)
+                .name("loadavg5")
+                .value(MetricValue::builder().time(time).value(1.5).build())
+                .build()])
+            .await
+            .unwrap();
+        assert_eq!(
+            client.list_host_metric_names(host_id).await,
+            Ok(vec!["loadavg5".to_owned()]),
+        );
+        assert_eq!(
+            client
+                .list_host_metric_values(host_id, "loadavg5", time, time)
+                .await,
+            Ok(vec![MetricValue::builder().time(time).value(1.5).build()]),
+        );
+        assert_eq!(fake.state().hosts.len(), 2);
+    }
+
+    #[async_std::test]
+    async fn monitors_and_alerts() {
+        let fake = FakeServer::new();
+        let client = client(&fake);
+        let monitor = client
+            .create_monitor(MonitorValue::Connectivity {
+                name: "connectivity".to_owned(),
+                memo: "".to_owned(),
+                scopes: vec![],
+                exclude_scopes: vec![],
+                alert_status_on_gone: AlertStatus::Critical,
+                notification_interval: None,
+                is_mute: false,
+                extra: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(client.list_monitors().await, Ok(vec![monitor.clone()]));
+        assert_eq!(client.delete_monitor(monitor.id).await, Ok(monitor.clone()));
+        assert!(client
+            .get_monitor(monitor.id)
+            .await
+            .is_err_and(|err| err.is_not_found()));
+
+        fake.state().alerts.extend((0..5).map(|i| {
+            Alert::new(
+                generate_id::<AlertValue>(),
+                AlertValue::builder()
+                    .status(AlertStatus::Critical)
+                    .monitor_type(MonitorType::Connectivity)
+                    .opened_at(DateTime::from_timestamp(1700000000 + i, 0).unwrap())
+                    .build(),
+            )
+        }));
+        let alert_id = fake.state().alerts[0].id;
+        client.update_alert(alert_id, "alert memo").await.unwrap();
+        assert_eq!(
+            client.get_alert(alert_id).await.unwrap().extra.get("memo"),
+            Some(&json!("alert memo")),
+        );
+        assert_eq!(
+            client
+                .list_alert_logs(alert_id, None::<AlertLogId>, 10)
+                .await,
+            Ok((vec![], None)),
+        );
+        client.close_alert(alert_id, "closed").await.unwrap();
+        let (logs, next_id) = client
+            .list_alert_logs(alert_id, None::<AlertLogId>, 10)
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].id, fake.state().alert_logs[&alert_id][0].id);
+        assert_eq!(logs[0].trigger, AlertLogTrigger::Manual);
+        assert_eq!(next_id, None);
+        assert_eq!(
+            client
+                .alerts_stream(false)
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+        let alerts = client
+            .alerts_stream(true)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            alerts
+                .iter()
+                .map(|alert| alert.id)
+                .collect::<Vec<AlertId>>(),
+            fake.state()
+                .alerts
+                .iter()
+                .rev()
+                .map(|alert| alert.id)
+                .collect::<Vec<_>>(),
+        );
+        assert!(alerts[4]
+            .closed_at
+            .is_some_and(|closed_at| closed_at <= Utc::now()));
+    }
+}
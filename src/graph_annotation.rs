@@ -36,6 +36,10 @@ pub struct GraphAnnotationValue {
     )]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub roles: Vec<RoleName>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -108,84 +112,101 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches graph annotations.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/graph-annotations#get>.
-    pub async fn list_graph_annotations(
-        &self,
-        service: impl Into<ServiceName>,
-        from: impl Into<DateTime<Utc>>,
-        to: impl Into<DateTime<Utc>>,
-    ) -> Result<Vec<GraphAnnotation>> {
-        self.request(
-            Method::GET,
-            "/api/v0/graph-annotations",
-            query_params! {
-                service = service.into().to_string(),
-                from = from.into().timestamp().to_string(),
-                to = to.into().timestamp().to_string(),
-            },
-            request_body![],
-            response_body! { graphAnnotations: Vec<GraphAnnotation> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches graph annotations.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/graph-annotations#get>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn list_graph_annotations(
+            &self,
+            service: impl Into<ServiceName>,
+            from: impl Into<DateTime<Utc>>,
+            to: impl Into<DateTime<Utc>>,
+        ) -> Result<Vec<GraphAnnotation>> {
+            let service_name = service.into();
+            record_field!(service_name, service_name);
+            self.request(
+                Method::GET,
+                "/api/v0/graph-annotations",
+                query_params! {
+                    service = service_name.to_string(),
+                    from = from.into().timestamp().to_string(),
+                    to = to.into().timestamp().to_string(),
+                },
+                request_body![],
+                response_body! { graphAnnotations: Vec<GraphAnnotation> },
+            )
+            .await
+        }
 
-    /// Creates a new graph annotation.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/graph-annotations#create>.
-    pub async fn create_graph_annotation(
-        &self,
-        graph_annotation_value: impl Borrow<GraphAnnotationValue>,
-    ) -> Result<GraphAnnotation> {
-        self.request(
-            Method::POST,
-            "/api/v0/graph-annotations",
-            query_params![],
-            request_body!(graph_annotation_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new graph annotation.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/graph-annotations#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_graph_annotation(
+            &self,
+            graph_annotation_value: impl Borrow<GraphAnnotationValue>,
+        ) -> Result<GraphAnnotation> {
+            self.request(
+                Method::POST,
+                "/api/v0/graph-annotations",
+                query_params![],
+                request_body!(graph_annotation_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Updates a graph annotation.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/graph-annotations#update>.
-    pub async fn update_graph_annotation(
-        &self,
-        graph_annotation_id: impl Into<GraphAnnotationId>,
-        graph_annotation_value: impl Borrow<GraphAnnotationValue>,
-    ) -> Result<GraphAnnotation> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/graph-annotations/{}", graph_annotation_id),
-            query_params![],
-            request_body!(graph_annotation_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Updates a graph annotation.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/graph-annotations#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(graph_annotation_id))
+        )]
+        pub async fn update_graph_annotation(
+            &self,
+            graph_annotation_id: impl Into<GraphAnnotationId>,
+            graph_annotation_value: impl Borrow<GraphAnnotationValue>,
+        ) -> Result<GraphAnnotation> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/graph-annotations/{}", graph_annotation_id),
+                query_params![],
+                request_body!(graph_annotation_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes a graph annotation.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/graph-annotations#delete>.
-    pub async fn delete_graph_annotation(
-        &self,
-        graph_annotation_id: impl Into<GraphAnnotationId>,
-    ) -> Result<GraphAnnotation> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/graph-annotations/{}", graph_annotation_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Deletes a graph annotation.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/graph-annotations#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(graph_annotation_id))
+        )]
+        pub async fn delete_graph_annotation(
+            &self,
+            graph_annotation_id: impl Into<GraphAnnotationId>,
+        ) -> Result<GraphAnnotation> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/graph-annotations/{}", graph_annotation_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
     use std::time::{Duration, SystemTime};
@@ -141,40 +141,44 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Creates new graph definitions.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/host-metrics#post-graphdef>.
-    pub async fn create_graph_definitions(
-        &self,
-        graph_definitions: impl IntoIterator<Item = GraphDefinition>,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/graph-defs/create",
-            query_params![],
-            request_body!(graph_definitions.into_iter().collect::<Vec<_>>()),
-            response_body!(),
-        )
-        .await
-    }
-
-    /// Deletes a graph definition.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/host-metrics#delete-graphdef>.
-    pub async fn delete_graph_definition(&self, name: impl AsRef<str>) -> Result<()> {
-        self.request(
-            Method::DELETE,
-            "/api/v0/graph-defs",
-            query_params![],
-            request_body! { name: String = name.as_ref().to_owned() },
-            response_body!(),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Creates new graph definitions.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/host-metrics#post-graphdef>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_graph_definitions(
+            &self,
+            graph_definitions: impl IntoIterator<Item = GraphDefinition>,
+        ) -> Result<()> {
+            self.request(
+                Method::POST,
+                "/api/v0/graph-defs/create",
+                query_params![],
+                request_body!(graph_definitions.into_iter().collect::<Vec<_>>()),
+                response_body!(),
+            )
+            .await
+        }
+
+        /// Deletes a graph definition.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/host-metrics#delete-graphdef>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn delete_graph_definition(&self, name: impl AsRef<str>) -> Result<()> {
+            self.request(
+                Method::DELETE,
+                "/api/v0/graph-defs",
+                query_params![],
+                request_body! { name: String = name.as_ref().to_owned() },
+                response_body!(),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use crate::graph_definition::*;
     use crate::tests::*;
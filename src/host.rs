@@ -116,6 +116,10 @@ pub struct HostValue {
     #[builder(default)]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub checks: Vec<HostCheck>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(PartialEq, Clone, Debug, TypedBuilder, Serialize, Deserialize)]
@@ -272,9 +276,43 @@ mod tests {
         })
     }
 
+    fn host_example3() -> Host {
+        Host::builder()
+            .id("host3")
+            .created_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+            .status(HostStatus::Working)
+            .value(
+                HostValue::builder()
+                    .name("example-host")
+                    .extra(
+                        json!({ "newSetting": { "key": "value" } })
+                            .as_object()
+                            .unwrap()
+                            .clone(),
+                    )
+                    .build(),
+            )
+            .build()
+    }
+
+    fn host_json_example3() -> serde_json::Value {
+        json!({
+            "id": "host3",
+            "createdAt": 1700000000,
+            "size": "standard",
+            "status": "working",
+            "isRetired": false,
+            "roles": {},
+            "name": "example-host",
+            "meta": {},
+            "newSetting": { "key": "value" },
+        })
+    }
+
     #[rstest]
     #[case(host_example1(), host_json_example1())]
     #[case(host_example2(), host_json_example2())]
+    #[case(host_example3(), host_json_example3())]
     fn test_host_json(#[case] host: Host, #[case] json: serde_json::Value) {
         assert_eq!(serde_json::to_value(&host).unwrap(), json);
         assert_eq!(host, serde_json::from_value(json).unwrap());
@@ -465,267 +503,288 @@ impl From<(ServiceName, RoleName)> for ListHostsParams {
     }
 }
 
-impl Client {
-    /// Creates a new host.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#create>.
-    pub async fn create_host(&self, host_value: impl Borrow<HostValue>) -> Result<HostId> {
-        self.request(
-            Method::POST,
-            "/api/v0/hosts",
-            query_params![],
-            request_body!(host_value.borrow()),
-            response_body! { id: HostId },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Creates a new host.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_host(&self, host_value: impl Borrow<HostValue>) -> Result<HostId> {
+            self.request(
+                Method::POST,
+                "/api/v0/hosts",
+                query_params![],
+                request_body!(host_value.borrow()),
+                response_body! { id: HostId },
+            )
+            .await
+        }
 
-    /// Gets a host.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#get>.
-    pub async fn get_host(&self, host_id: impl Into<HostId>) -> Result<Host> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/hosts/{}", host_id),
-            query_params![],
-            request_body![],
-            response_body! { host: Host },
-        )
-        .await
-    }
+        /// Gets a host.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn get_host(&self, host_id: impl Into<HostId>) -> Result<Host> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/hosts/{}", host_id),
+                query_params![],
+                request_body![],
+                response_body! { host: Host },
+            )
+            .await
+        }
 
-    /// Gets a host by the custom identifier.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#get-by-custom-identifier>.
-    pub async fn get_host_by_custom_identifier(
-        &self,
-        custom_identifier: impl AsRef<str>,
-    ) -> Result<Option<Host>> {
-        self.request(
-            Method::GET,
-            format!(
-                "/api/v0/hosts-by-custom-identifier/{}",
-                form_urlencoded::byte_serialize(custom_identifier.as_ref().as_bytes())
-                    .collect::<String>(),
-            ),
-            query_params![],
-            request_body![],
-            response_body! { host: Option<Host> },
-        )
-        .await
-    }
+        /// Gets a host by the custom identifier.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#get-by-custom-identifier>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn get_host_by_custom_identifier(
+            &self,
+            custom_identifier: impl AsRef<str>,
+        ) -> Result<Option<Host>> {
+            self.request(
+                Method::GET,
+                format!(
+                    "/api/v0/hosts-by-custom-identifier/{}",
+                    form_urlencoded::byte_serialize(custom_identifier.as_ref().as_bytes())
+                        .collect::<String>(),
+                ),
+                query_params![],
+                request_body![],
+                response_body! { host: Option<Host> },
+            )
+            .await
+        }
 
-    /// Updates a host.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#update-information>.
-    pub async fn update_host(
-        &self,
-        host_id: impl Into<HostId>,
-        host_value: impl Borrow<HostValue>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/hosts/{}", host_id),
-            query_params![],
-            request_body!(host_value.borrow()),
-            response_body!(),
-        )
-        .await
-    }
+        /// Updates a host.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#update-information>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn update_host(
+            &self,
+            host_id: impl Into<HostId>,
+            host_value: impl Borrow<HostValue>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/hosts/{}", host_id),
+                query_params![],
+                request_body!(host_value.borrow()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Updates host status.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#update-status>.
-    pub async fn update_host_status(
-        &self,
-        host_id: impl Into<HostId>,
-        host_status: HostStatus,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            format_url!("/api/v0/hosts/{}/status", host_id),
-            query_params![],
-            request_body! { status: HostStatus = host_status },
-            response_body!(),
-        )
-        .await
-    }
+        /// Updates host status.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#update-status>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn update_host_status(
+            &self,
+            host_id: impl Into<HostId>,
+            host_status: HostStatus,
+        ) -> Result<()> {
+            self.request(
+                Method::POST,
+                format_url!("/api/v0/hosts/{}/status", host_id),
+                query_params![],
+                request_body! { status: HostStatus = host_status },
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Updates host statuses.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#update-status>.
-    pub async fn update_host_statuses(
-        &self,
-        host_ids: impl IntoIterator<Item = impl Into<HostId>>,
-        host_status: HostStatus,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/hosts/bulk-update-statuses",
-            query_params![],
-            request_body! {
-                ids: Vec<HostId> = host_ids
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<_>>(),
-                status: HostStatus = host_status,
-            },
-            response_body!(),
-        )
-        .await
-    }
+        /// Updates host statuses.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#update-status>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_ids)))]
+        pub async fn update_host_statuses(
+            &self,
+            host_ids: impl IntoIterator<Item = impl Into<HostId>>,
+            host_status: HostStatus,
+        ) -> Result<()> {
+            let host_ids = host_ids
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<HostId>>();
+            record_field!(host_ids, join_ids(&host_ids));
+            self.request(
+                Method::POST,
+                "/api/v0/hosts/bulk-update-statuses",
+                query_params![],
+                request_body! {
+                    ids: Vec<HostId> = host_ids,
+                    status: HostStatus = host_status,
+                },
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Updates host roles.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#update-roles>.
-    pub async fn update_host_roles(
-        &self,
-        host_id: impl Into<HostId>,
-        role_fullnames: impl IntoIterator<Item = impl Into<RoleFullname>>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/hosts/{}/role-fullnames", host_id),
-            query_params![],
-            request_body! {
-                roleFullnames: Vec<RoleFullname> = role_fullnames
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<_>>(),
-            },
-            response_body!(),
-        )
-        .await
-    }
+        /// Updates host roles.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#update-roles>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn update_host_roles(
+            &self,
+            host_id: impl Into<HostId>,
+            role_fullnames: impl IntoIterator<Item = impl Into<RoleFullname>>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/hosts/{}/role-fullnames", host_id),
+                query_params![],
+                request_body! {
+                    roleFullnames: Vec<RoleFullname> = role_fullnames
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<_>>(),
+                },
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Retires a host.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#retire>.
-    pub async fn retire_host(&self, host_id: impl Into<HostId>) -> Result<()> {
-        self.request(
-            Method::POST,
-            format_url!("/api/v0/hosts/{}/retire", host_id),
-            query_params![],
-            request_body![],
-            response_body!(),
-        )
-        .await
-    }
+        /// Retires a host.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#retire>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn retire_host(&self, host_id: impl Into<HostId>) -> Result<()> {
+            self.request(
+                Method::POST,
+                format_url!("/api/v0/hosts/{}/retire", host_id),
+                query_params![],
+                request_body![],
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Retires hosts.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#bulk-retire>.
-    pub async fn retire_hosts(
-        &self,
-        host_ids: impl IntoIterator<Item = impl Into<HostId>>,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/hosts/bulk-retire",
-            query_params![],
-            request_body! {
-                ids: Vec<HostId> = host_ids
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<_>>(),
-            },
-            response_body!(),
-        )
-        .await
-    }
+        /// Retires hosts.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#bulk-retire>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_ids)))]
+        pub async fn retire_hosts(
+            &self,
+            host_ids: impl IntoIterator<Item = impl Into<HostId>>,
+        ) -> Result<()> {
+            let host_ids = host_ids
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<HostId>>();
+            record_field!(host_ids, join_ids(&host_ids));
+            self.request(
+                Method::POST,
+                "/api/v0/hosts/bulk-retire",
+                query_params![],
+                request_body! {
+                    ids: Vec<HostId> = host_ids,
+                },
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Fetches hosts.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#list>.
-    ///
-    /// ```rust,no_run
-    /// # use mackerel_client::Client;
-    /// # use mackerel_client::host::{ListHostsParams, HostStatus};
-    /// # use mackerel_client::role::{RoleFullname, RoleName};
-    /// # use mackerel_client::service::ServiceName;
-    /// #
-    /// # #[async_std::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = Client::new("<Mackerel-API-KEY>");
-    /// // Fetches all the hosts (with working or standby status).
-    /// let hosts = client.list_hosts(()).await?;
-    /// // Fetches the hosts in the specified service and role.
-    /// let hosts = client.list_hosts(ServiceName::from("service0")).await?;
-    /// let hosts = client.list_hosts(RoleFullname::from("service0:role0")).await?;
-    /// let hosts = client.list_hosts((ServiceName::from("service0"), RoleName::from("role0"))).await?;
-    /// // Fetches the hosts with the specified name.
-    /// let hosts = client.list_hosts(ListHostsParams::host_name("example-host")).await?;
-    ///
-    /// // Fetches the hosts with the specified statuses.
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::default().status(HostStatus::Working),
-    /// ).await?;
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::service_name("service0").status(HostStatus::Working),
-    /// ).await?;
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::service_name("service0")
-    ///         .statuses([HostStatus::Working, HostStatus::Standby, HostStatus::Maintenance]),
-    /// ).await?;
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::role_fullname("service0:role0").status(HostStatus::Working),
-    /// ).await?;
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::service_role_name("service0", "role0").status(HostStatus::Working),
-    /// ).await?;
-    /// let hosts = client.list_hosts(
-    ///     ListHostsParams::service_role_names("service0", ["role0", "role1", "role2"])
-    ///         .statuses([HostStatus::Working, HostStatus::Standby, HostStatus::Maintenance]),
-    /// ).await?;
-    /// # Ok(())
-    /// # }
-    pub async fn list_hosts(
-        &self,
-        list_hosts_params: impl Into<ListHostsParams>,
-    ) -> Result<Vec<Host>> {
-        self.request(
-            Method::GET,
-            "/api/v0/hosts",
-            &list_hosts_params.into().query_params(),
-            request_body![],
-            response_body! { hosts: Vec<Host> },
-        )
-        .await
-    }
+        /// Fetches hosts.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#list>.
+        ///
+        /// ```rust,no_run
+        /// # use mackerel_client::Client;
+        /// # use mackerel_client::host::{ListHostsParams, HostStatus};
+        /// # use mackerel_client::role::{RoleFullname, RoleName};
+        /// # use mackerel_client::service::ServiceName;
+        /// #
+        /// # #[async_std::main]
+        /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+        /// # let client = Client::new("<Mackerel-API-KEY>");
+        /// // Fetches all the hosts (with working or standby status).
+        /// let hosts = client.list_hosts(()).await?;
+        /// // Fetches the hosts in the specified service and role.
+        /// let hosts = client.list_hosts(ServiceName::from("service0")).await?;
+        /// let hosts = client.list_hosts(RoleFullname::from("service0:role0")).await?;
+        /// let hosts = client.list_hosts((ServiceName::from("service0"), RoleName::from("role0"))).await?;
+        /// // Fetches the hosts with the specified name.
+        /// let hosts = client.list_hosts(ListHostsParams::host_name("example-host")).await?;
+        ///
+        /// // Fetches the hosts with the specified statuses.
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::default().status(HostStatus::Working),
+        /// ).await?;
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::service_name("service0").status(HostStatus::Working),
+        /// ).await?;
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::service_name("service0")
+        ///         .statuses([HostStatus::Working, HostStatus::Standby, HostStatus::Maintenance]),
+        /// ).await?;
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::role_fullname("service0:role0").status(HostStatus::Working),
+        /// ).await?;
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::service_role_name("service0", "role0").status(HostStatus::Working),
+        /// ).await?;
+        /// let hosts = client.list_hosts(
+        ///     ListHostsParams::service_role_names("service0", ["role0", "role1", "role2"])
+        ///         .statuses([HostStatus::Working, HostStatus::Standby, HostStatus::Maintenance]),
+        /// ).await?;
+        /// # Ok(())
+        /// # }
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_hosts(
+            &self,
+            list_hosts_params: impl Into<ListHostsParams>,
+        ) -> Result<Vec<Host>> {
+            self.request(
+                Method::GET,
+                "/api/v0/hosts",
+                &list_hosts_params.into().query_params(),
+                request_body![],
+                response_body! { hosts: Vec<Host> },
+            )
+            .await
+        }
 
-    /// Fetches host metric names.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#metric-names>.
-    pub async fn list_host_metric_names(&self, host_id: impl Into<HostId>) -> Result<Vec<String>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/hosts/{}/metric-names", host_id),
-            query_params![],
-            request_body![],
-            response_body! { names: Vec<String> },
-        )
-        .await
-    }
+        /// Fetches host metric names.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#metric-names>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn list_host_metric_names(
+            &self,
+            host_id: impl Into<HostId>,
+        ) -> Result<Vec<String>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/hosts/{}/metric-names", host_id),
+                query_params![],
+                request_body![],
+                response_body! { names: Vec<String> },
+            )
+            .await
+        }
 
-    /// Fetches host monitoring statuses.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/hosts#monitored-statuses>.
-    pub async fn list_host_monitored_statuses(
-        &self,
-        host_id: impl Into<HostId>,
-    ) -> Result<Vec<MonitoredStatus>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/hosts/{}/monitored-statuses", host_id),
-            query_params![],
-            request_body![],
-            response_body! { monitoredStatuses: Vec<MonitoredStatus> },
-        )
-        .await
+        /// Fetches host monitoring statuses.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/hosts#monitored-statuses>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn list_host_monitored_statuses(
+            &self,
+            host_id: impl Into<HostId>,
+        ) -> Result<Vec<MonitoredStatus>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/hosts/{}/monitored-statuses", host_id),
+                query_params![],
+                request_body![],
+                response_body! { monitoredStatuses: Vec<MonitoredStatus> },
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use rstest::rstest;
     use serde_json::json;
@@ -33,6 +33,10 @@ impl std::ops::Deref for Invitation {
 pub struct InvitationValue {
     pub email: String,
     pub authority: UserAuthority,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -90,54 +94,59 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the invitations.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/invitations#list>.
-    pub async fn list_invitations(&self) -> Result<Vec<Invitation>> {
-        self.request(
-            Method::GET,
-            "/api/v0/invitations",
-            query_params![],
-            request_body![],
-            response_body! { invitations: Vec<Invitation> },
-        )
-        .await
-    }
-
-    /// Creates a new invitation.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/invitations#create>.
-    pub async fn create_invitation(
-        &self,
-        invitation_value: impl Borrow<InvitationValue>,
-    ) -> Result<Invitation> {
-        self.request(
-            Method::POST,
-            "/api/v0/invitations",
-            query_params![],
-            request_body!(invitation_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Revokes an invitation.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/invitations#revoke>.
-    pub async fn revoke_invitation(&self, email: impl AsRef<str>) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/invitations/revoke",
-            query_params![],
-            request_body! { email: String = email.as_ref().to_owned() },
-            response_body!(),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Fetches all the invitations.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/invitations#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_invitations(&self) -> Result<Vec<Invitation>> {
+            self.request(
+                Method::GET,
+                "/api/v0/invitations",
+                query_params![],
+                request_body![],
+                response_body! { invitations: Vec<Invitation> },
+            )
+            .await
+        }
+
+        /// Creates a new invitation.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/invitations#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_invitation(
+            &self,
+            invitation_value: impl Borrow<InvitationValue>,
+        ) -> Result<Invitation> {
+            self.request(
+                Method::POST,
+                "/api/v0/invitations",
+                query_params![],
+                request_body!(invitation_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Revokes an invitation.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/invitations#revoke>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn revoke_invitation(&self, email: impl AsRef<str>) -> Result<()> {
+            self.request(
+                Method::POST,
+                "/api/v0/invitations/revoke",
+                query_params![],
+                request_body! { email: String = email.as_ref().to_owned() },
+                response_body!(),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
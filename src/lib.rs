@@ -28,13 +28,39 @@
 //! # }
 //! ```
 //!
+//! # Features
+//!
+//! - `reqwest` (default): The default transport based on [reqwest](https://docs.rs/reqwest).
+//! - `blocking`: The blocking API client [`blocking::Client`].
+//! - `tracing`: Emits [tracing](https://docs.rs/tracing) spans of each API method and HTTP request,
+//!   with the entity ids, method, path, status code, retry count and latency.
+//!   The API key is never recorded.
+//! - `test-util`: The mock server macros [`test_server!`] and [`test_client!`],
+//!   and the canned entities in [`test_util::fixtures`] for tests of the downstream crates.
+//! - `fake-server`: The in-process fake Mackerel server [`fake::FakeServer`] for end-to-end tests.
+//!
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cassette;
 pub mod client;
+pub mod config;
+pub mod dry_run;
 pub mod entity;
 pub mod error;
+#[cfg(feature = "fake-server")]
+pub mod fake;
+pub mod middleware;
+pub mod monitor_diff;
+pub mod monitor_sync;
 pub(crate) mod name;
+pub mod rate_limit;
+pub mod retry;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 #[cfg(test)]
 pub(crate) mod tests;
+pub mod transport;
 
 pub mod alert;
 pub mod alert_group_setting;
@@ -43,6 +69,7 @@ pub mod channel;
 pub mod check_report;
 pub mod dashboard;
 pub mod downtime;
+pub mod expression;
 pub mod graph_annotation;
 pub mod graph_definition;
 pub mod host;
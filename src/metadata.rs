@@ -15,269 +15,319 @@ pub struct Metadata {
     pub namespace: String,
 }
 
-impl Client {
-    /// Retrieves a host metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#hostget>.
-    pub async fn get_host_metadata(
-        &self,
-        host_id: impl Into<HostId>,
-        namespace: impl AsRef<str>,
-    ) -> Result<serde_json::Value> {
-        self.request(
-            Method::GET,
-            format!(
-                "/api/v0/hosts/{}/metadata/{}",
-                host_id.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Retrieves a host metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#hostget>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn get_host_metadata(
+            &self,
+            host_id: impl Into<HostId>,
+            namespace: impl AsRef<str>,
+        ) -> Result<serde_json::Value> {
+            self.request(
+                Method::GET,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/hosts/{}/metadata", host_id),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Creates/Updates a host metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#hostput>.
-    pub async fn put_host_metadata(
-        &self,
-        host_id: impl Into<HostId>,
-        namespace: impl AsRef<str>,
-        metadata: impl Borrow<serde_json::Value>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format!(
-                "/api/v0/hosts/{}/metadata/{}",
-                host_id.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body!(metadata.borrow()),
-            response_body!(),
-        )
-        .await
-    }
+        /// Creates/Updates a host metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#hostput>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn put_host_metadata(
+            &self,
+            host_id: impl Into<HostId>,
+            namespace: impl AsRef<str>,
+            metadata: impl Borrow<serde_json::Value>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/hosts/{}/metadata", host_id),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body!(metadata.borrow()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Deletes a host metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#hostdelete>.
-    pub async fn delete_host_metadata(
-        &self,
-        host_id: impl Into<HostId>,
-        namespace: impl AsRef<str>,
-    ) -> Result<()> {
-        self.request(
-            Method::DELETE,
-            format!(
-                "/api/v0/hosts/{}/metadata/{}",
-                host_id.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(),
-        )
-        .await
-    }
+        /// Deletes a host metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#hostdelete>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn delete_host_metadata(
+            &self,
+            host_id: impl Into<HostId>,
+            namespace: impl AsRef<str>,
+        ) -> Result<()> {
+            self.request(
+                Method::DELETE,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/hosts/{}/metadata", host_id),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Lists host metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#hostlist>.
-    pub async fn list_host_metadata(&self, host_id: impl Into<HostId>) -> Result<Vec<Metadata>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/hosts/{}/metadata", host_id),
-            query_params![],
-            request_body![],
-            response_body! { metadata: Vec<Metadata> },
-        )
-        .await
-    }
+        /// Lists host metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#hostlist>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn list_host_metadata(
+            &self,
+            host_id: impl Into<HostId>,
+        ) -> Result<Vec<Metadata>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/hosts/{}/metadata", host_id),
+                query_params![],
+                request_body![],
+                response_body! { metadata: Vec<Metadata> },
+            )
+            .await
+        }
 
-    /// Retrieves a service metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#serviceget>.
-    pub async fn get_service_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        namespace: impl AsRef<str>,
-    ) -> Result<serde_json::Value> {
-        self.request(
-            Method::GET,
-            format!(
-                "/api/v0/services/{}/metadata/{}",
-                service_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Retrieves a service metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#serviceget>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn get_service_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            namespace: impl AsRef<str>,
+        ) -> Result<serde_json::Value> {
+            self.request(
+                Method::GET,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/services/{}/metadata", service_name),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Creates/Updates a service metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#serviceput>.
-    pub async fn put_service_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        namespace: impl AsRef<str>,
-        metadata: impl Borrow<serde_json::Value>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format!(
-                "/api/v0/services/{}/metadata/{}",
-                service_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body!(metadata.borrow()),
-            response_body!(),
-        )
-        .await
-    }
+        /// Creates/Updates a service metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#serviceput>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn put_service_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            namespace: impl AsRef<str>,
+            metadata: impl Borrow<serde_json::Value>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/services/{}/metadata", service_name),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body!(metadata.borrow()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Deletes a service metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#servicedelete>.
-    pub async fn delete_service_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        namespace: impl AsRef<str>,
-    ) -> Result<()> {
-        self.request(
-            Method::DELETE,
-            format!(
-                "/api/v0/services/{}/metadata/{}",
-                service_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(),
-        )
-        .await
-    }
+        /// Deletes a service metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#servicedelete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn delete_service_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            namespace: impl AsRef<str>,
+        ) -> Result<()> {
+            self.request(
+                Method::DELETE,
+                format!(
+                    "{}/{}",
+                    format_url!("/api/v0/services/{}/metadata", service_name),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Lists service metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#servicelist>.
-    pub async fn list_service_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-    ) -> Result<Vec<Metadata>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/services/{}/metadata", service_name),
-            query_params![],
-            request_body![],
-            response_body! { metadata: Vec<Metadata> },
-        )
-        .await
-    }
+        /// Lists service metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#servicelist>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn list_service_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+        ) -> Result<Vec<Metadata>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/services/{}/metadata", service_name),
+                query_params![],
+                request_body![],
+                response_body! { metadata: Vec<Metadata> },
+            )
+            .await
+        }
 
-    /// Retrieves a role metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#roleget>.
-    pub async fn get_role_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role_name: impl Into<RoleName>,
-        namespace: impl AsRef<str>,
-    ) -> Result<serde_json::Value> {
-        self.request(
-            Method::GET,
-            format!(
-                "/api/v0/services/{}/roles/{}/metadata/{}",
-                service_name.into(),
-                role_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Retrieves a role metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#roleget>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name, role_name))
+        )]
+        pub async fn get_role_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role_name: impl Into<RoleName>,
+            namespace: impl AsRef<str>,
+        ) -> Result<serde_json::Value> {
+            self.request(
+                Method::GET,
+                format!(
+                    "{}/{}",
+                    format_url!(
+                        "/api/v0/services/{}/roles/{}/metadata",
+                        service_name,
+                        role_name
+                    ),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Creates/Updates a role metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#roleput>.
-    pub async fn put_role_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role_name: impl Into<RoleName>,
-        namespace: impl AsRef<str>,
-        metadata: impl Borrow<serde_json::Value>,
-    ) -> Result<()> {
-        self.request(
-            Method::PUT,
-            format!(
-                "/api/v0/services/{}/roles/{}/metadata/{}",
-                service_name.into(),
-                role_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body!(metadata.borrow()),
-            response_body!(),
-        )
-        .await
-    }
+        /// Creates/Updates a role metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#roleput>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name, role_name))
+        )]
+        pub async fn put_role_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role_name: impl Into<RoleName>,
+            namespace: impl AsRef<str>,
+            metadata: impl Borrow<serde_json::Value>,
+        ) -> Result<()> {
+            self.request(
+                Method::PUT,
+                format!(
+                    "{}/{}",
+                    format_url!(
+                        "/api/v0/services/{}/roles/{}/metadata",
+                        service_name,
+                        role_name
+                    ),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body!(metadata.borrow()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Deletes a role metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#roledelete>.
-    pub async fn delete_role_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role_name: impl Into<RoleName>,
-        namespace: impl AsRef<str>,
-    ) -> Result<()> {
-        self.request(
-            Method::DELETE,
-            format!(
-                "/api/v0/services/{}/roles/{}/metadata/{}",
-                service_name.into(),
-                role_name.into(),
-                namespace.as_ref()
-            ),
-            query_params![],
-            request_body![],
-            response_body!(),
-        )
-        .await
-    }
+        /// Deletes a role metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#roledelete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name, role_name))
+        )]
+        pub async fn delete_role_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role_name: impl Into<RoleName>,
+            namespace: impl AsRef<str>,
+        ) -> Result<()> {
+            self.request(
+                Method::DELETE,
+                format!(
+                    "{}/{}",
+                    format_url!(
+                        "/api/v0/services/{}/roles/{}/metadata",
+                        service_name,
+                        role_name
+                    ),
+                    namespace.as_ref()
+                ),
+                query_params![],
+                request_body![],
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Lists role metadata.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/metadata#rolelist>.
-    pub async fn list_role_metadata(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role_name: impl Into<RoleName>,
-    ) -> Result<Vec<Metadata>> {
-        self.request(
-            Method::GET,
-            format_url!(
-                "/api/v0/services/{}/roles/{}/metadata",
-                service_name,
-                role_name,
-            ),
-            query_params![],
-            request_body![],
-            response_body! { metadata: Vec<Metadata> },
-        )
-        .await
+        /// Lists role metadata.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/metadata#rolelist>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name, role_name))
+        )]
+        pub async fn list_role_metadata(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role_name: impl Into<RoleName>,
+        ) -> Result<Vec<Metadata>> {
+            self.request(
+                Method::GET,
+                format_url!(
+                    "/api/v0/services/{}/roles/{}/metadata",
+                    service_name,
+                    role_name,
+                ),
+                query_params![],
+                request_body![],
+                response_body! { metadata: Vec<Metadata> },
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
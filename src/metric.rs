@@ -100,118 +100,138 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Posts host metric values.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/host-metrics#post>.
-    pub async fn post_host_metric_values(
-        &self,
-        host_metric_values: impl IntoIterator<Item = HostMetricValue>,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            "/api/v0/tsdb",
-            query_params![],
-            request_body!(host_metric_values.into_iter().collect::<Vec<_>>()),
-            response_body!(),
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Posts host metric values.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/host-metrics#post>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn post_host_metric_values(
+            &self,
+            host_metric_values: impl IntoIterator<Item = HostMetricValue>,
+        ) -> Result<()> {
+            self.request(
+                Method::POST,
+                "/api/v0/tsdb",
+                query_params![],
+                request_body!(host_metric_values.into_iter().collect::<Vec<_>>()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Fetches host metric value.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/host-metrics#get>.
-    pub async fn list_host_metric_values(
-        &self,
-        host_id: impl Into<HostId>,
-        metric_name: impl AsRef<str>,
-        from: impl Into<DateTime<Utc>>,
-        to: impl Into<DateTime<Utc>>,
-    ) -> Result<Vec<MetricValue>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/hosts/{}/metrics", host_id),
-            query_params! {
-                name = metric_name.as_ref().to_owned(),
-                from = from.into().timestamp().to_string(),
-                to = to.into().timestamp().to_string(),
-            },
-            request_body![],
-            response_body! { metrics: Vec<MetricValue> },
-        )
-        .await
-    }
+        /// Fetches host metric value.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/host-metrics#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_id)))]
+        pub async fn list_host_metric_values(
+            &self,
+            host_id: impl Into<HostId>,
+            metric_name: impl AsRef<str>,
+            from: impl Into<DateTime<Utc>>,
+            to: impl Into<DateTime<Utc>>,
+        ) -> Result<Vec<MetricValue>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/hosts/{}/metrics", host_id),
+                query_params! {
+                    name = metric_name.as_ref().to_owned(),
+                    from = from.into().timestamp().to_string(),
+                    to = to.into().timestamp().to_string(),
+                },
+                request_body![],
+                response_body! { metrics: Vec<MetricValue> },
+            )
+            .await
+        }
 
-    /// Fetches latest host metric values of hosts.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/host-metrics#get-latest>.
-    pub async fn list_latest_host_metric_values(
-        &self,
-        host_ids: impl IntoIterator<Item = impl Into<HostId>>,
-        metric_names: impl IntoIterator<Item = impl AsRef<str>>,
-    ) -> Result<HashMap<HostId, HashMap<String, Option<MetricValue>>>> {
-        self.request(
-            Method::GET,
-            "/api/v0/tsdb/latest",
-            &host_ids
+        /// Fetches latest host metric values of hosts.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/host-metrics#get-latest>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(host_ids)))]
+        pub async fn list_latest_host_metric_values(
+            &self,
+            host_ids: impl IntoIterator<Item = impl Into<HostId>>,
+            metric_names: impl IntoIterator<Item = impl AsRef<str>>,
+        ) -> Result<HashMap<HostId, HashMap<String, Option<MetricValue>>>> {
+            let host_ids = host_ids
                 .into_iter()
-                .map(|host_id| ("hostId", host_id.into().to_string()))
-                .chain(
-                    metric_names
-                        .into_iter()
-                        .map(|metric_name| ("name", metric_name.as_ref().to_owned())),
-                )
-                .collect::<Vec<_>>(),
-            request_body![],
-            response_body! { tsdbLatest: HashMap<HostId, HashMap<String, Option<MetricValue>>> },
-        )
-        .await
-    }
+                .map(Into::into)
+                .collect::<Vec<HostId>>();
+            record_field!(host_ids, join_ids(&host_ids));
+            self.request(
+                Method::GET,
+                "/api/v0/tsdb/latest",
+                &host_ids
+                    .iter()
+                    .map(|host_id| ("hostId", host_id.to_string()))
+                    .chain(
+                        metric_names
+                            .into_iter()
+                            .map(|metric_name| ("name", metric_name.as_ref().to_owned())),
+                    )
+                    .collect::<Vec<_>>(),
+                request_body![],
+                response_body! {
+                    tsdbLatest: HashMap<HostId, HashMap<String, Option<MetricValue>>>,
+                },
+            )
+            .await
+        }
 
-    /// Posts service metric values.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/service-metrics#post>.
-    pub async fn post_service_metric_values(
-        &self,
-        service_name: impl Into<ServiceName>,
-        service_metric_values: impl IntoIterator<Item = ServiceMetricValue>,
-    ) -> Result<()> {
-        self.request(
-            Method::POST,
-            format_url!("/api/v0/services/{}/tsdb", service_name),
-            query_params![],
-            request_body!(service_metric_values.into_iter().collect::<Vec<_>>()),
-            response_body!(),
-        )
-        .await
-    }
+        /// Posts service metric values.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/service-metrics#post>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn post_service_metric_values(
+            &self,
+            service_name: impl Into<ServiceName>,
+            service_metric_values: impl IntoIterator<Item = ServiceMetricValue>,
+        ) -> Result<()> {
+            self.request(
+                Method::POST,
+                format_url!("/api/v0/services/{}/tsdb", service_name),
+                query_params![],
+                request_body!(service_metric_values.into_iter().collect::<Vec<_>>()),
+                response_body!(),
+            )
+            .await
+        }
 
-    /// Fetches service metric values.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/service-metrics#get>.
-    pub async fn list_service_metric_values(
-        &self,
-        service_name: impl Into<ServiceName>,
-        metric_name: impl AsRef<str>,
-        from: impl Into<DateTime<Utc>>,
-        to: impl Into<DateTime<Utc>>,
-    ) -> Result<Vec<MetricValue>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/services/{}/metrics", service_name),
-            query_params! {
-                name = metric_name.as_ref().to_owned(),
-                from = from.into().timestamp().to_string(),
-                to = to.into().timestamp().to_string(),
-            },
-            request_body![],
-            response_body! { metrics: Vec<MetricValue> },
-        )
-        .await
+        /// Fetches service metric values.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/service-metrics#get>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn list_service_metric_values(
+            &self,
+            service_name: impl Into<ServiceName>,
+            metric_name: impl AsRef<str>,
+            from: impl Into<DateTime<Utc>>,
+            to: impl Into<DateTime<Utc>>,
+        ) -> Result<Vec<MetricValue>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/services/{}/metrics", service_name),
+                query_params! {
+                    name = metric_name.as_ref().to_owned(),
+                    from = from.into().timestamp().to_string(),
+                    to = to.into().timestamp().to_string(),
+                },
+                request_body![],
+                response_body! { metrics: Vec<MetricValue> },
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use crate::metric::*;
     use crate::tests::*;
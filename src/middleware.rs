@@ -0,0 +1,208 @@
+//! Request/response middleware of the API client
+use std::sync::Arc;
+
+use crate::transport::{Transport, TransportFuture};
+
+/// A middleware wraps each request sent by the API client.
+///
+/// The middleware receives the request (method, url, headers and body) and
+/// [`Next`] to proceed to the following middleware and the transport.
+/// It can modify the request, inspect the response (status and duration),
+/// or short-circuit by returning a response without calling [`Next::run()`].
+/// The middleware runs on each attempt when the request is retried.
+#[cfg_attr(feature = "reqwest", doc = "```rust")]
+#[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+/// use mackerel_client::middleware::{Middleware, Next};
+/// use mackerel_client::transport::TransportFuture;
+/// use mackerel_client::Client;
+/// use std::time::Instant;
+///
+/// #[derive(Debug)]
+/// struct LoggingMiddleware;
+///
+/// impl Middleware for LoggingMiddleware {
+///     fn handle<'a>(
+///         &'a self,
+///         mut request: http::Request<Vec<u8>>,
+///         next: Next<'a>,
+///     ) -> TransportFuture<'a> {
+///         Box::pin(async move {
+///             request
+///                 .headers_mut()
+///                 .insert("x-request-id", http::HeaderValue::from_static("request-id"));
+///             let (method, uri) = (request.method().clone(), request.uri().clone());
+///             let start = Instant::now();
+///             let result = next.run(request).await;
+///             if let Ok(response) = &result {
+///                 println!("{} {} {} {:?}", method, uri, response.status(), start.elapsed());
+///             }
+///             result
+///         })
+///     }
+/// }
+///
+/// let client = Client::builder()
+///     .api_key("<Mackerel-API-KEY>")
+///     .middleware(LoggingMiddleware)
+///     .build();
+/// ```
+pub trait Middleware: std::fmt::Debug + Send + Sync {
+    /// Handles the request, and calls [`Next::run()`] to proceed.
+    fn handle<'a>(&'a self, request: http::Request<Vec<u8>>, next: Next<'a>)
+        -> TransportFuture<'a>;
+}
+
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn handle<'a>(
+        &'a self,
+        request: http::Request<Vec<u8>>,
+        next: Next<'a>,
+    ) -> TransportFuture<'a> {
+        (**self).handle(request, next)
+    }
+}
+
+/// The rest of the middleware chain, followed by the transport.
+#[derive(Clone, Copy, Debug)]
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middlewares: &'a [Arc<dyn Middleware>],
+        transport: &'a dyn Transport,
+    ) -> Self {
+        Self {
+            middlewares,
+            transport,
+        }
+    }
+
+    /// Sends the request to the next middleware, or the transport at the end of the chain.
+    pub fn run(self, request: http::Request<Vec<u8>>) -> TransportFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => middleware.handle(
+                request,
+                Next {
+                    middlewares,
+                    transport: self.transport,
+                },
+            ),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    use crate::client::*;
+    use crate::error::*;
+    use crate::middleware::*;
+    use crate::tests::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingMiddleware {
+        records: Mutex<Vec<String>>,
+    }
+
+    impl Middleware for RecordingMiddleware {
+        fn handle<'a>(
+            &'a self,
+            mut request: http::Request<Vec<u8>>,
+            next: Next<'a>,
+        ) -> TransportFuture<'a> {
+            Box::pin(async move {
+                request
+                    .headers_mut()
+                    .insert("x-request-id", http::HeaderValue::from_static("test"));
+                let record = format!(
+                    "{} {} {}",
+                    request.method(),
+                    request.uri().path(),
+                    String::from_utf8_lossy(request.body()),
+                );
+                let result = next.run(request).await;
+                let status = result.as_ref().map(|response| response.status());
+                self.records
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {:?}", record, status.ok()));
+                result
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct ShortCircuitMiddleware;
+
+    impl Middleware for ShortCircuitMiddleware {
+        fn handle<'a>(
+            &'a self,
+            request: http::Request<Vec<u8>>,
+            next: Next<'a>,
+        ) -> TransportFuture<'a> {
+            if request.method() == Method::GET {
+                return next.run(request);
+            }
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(StatusCode::FORBIDDEN)
+                    .body(json!({ "error": "read only" }).to_string().into_bytes())
+                    .unwrap())
+            })
+        }
+    }
+
+    impl Client {
+        async fn test_request(&self, method: Method) -> Result<bool> {
+            self.request(
+                method,
+                "/api/v0/test",
+                query_params![],
+                request_body! { message: String = "request body".to_owned() },
+                response_body!(..),
+            )
+            .await
+        }
+    }
+
+    #[async_std::test]
+    async fn middleware() {
+        let server = TEST_SERVER_POOL.get_server();
+        {
+            use httptest::{matchers::*, responders::*, Expectation};
+            server.expect(
+                Expectation::matching(all_of![
+                    request::method_path("GET", "/api/v0/test"),
+                    request::headers(contains(("x-request-id", "test"))),
+                ])
+                .respond_with(status_code(200).body("true")),
+            );
+        }
+        let recording_middleware = Arc::new(RecordingMiddleware::default());
+        let client = Client::builder()
+            .api_key("")
+            .api_base(server.url_str("/"))
+            .middleware(recording_middleware.clone())
+            .middleware(ShortCircuitMiddleware)
+            .build();
+        assert_eq!(client.test_request(Method::GET).await, Ok(true));
+        assert!(client
+            .test_request(Method::POST)
+            .await
+            .is_err_and(|err| err.status() == Some(StatusCode::FORBIDDEN)));
+        assert_eq!(
+            *recording_middleware.records.lock().unwrap(),
+            vec![
+                r#"GET /api/v0/test {"message":"request body"} Some(200)"#,
+                r#"POST /api/v0/test {"message":"request body"} Some(403)"#,
+            ],
+        );
+    }
+}
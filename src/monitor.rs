@@ -1,18 +1,24 @@
 use chrono::{DateTime, Utc};
+use futures_util::{future, stream, StreamExt};
 use http::Method;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::{skip_serializing_none, DeserializeFromStr, SerializeDisplay};
 use std::borrow::Borrow;
-use strum::{Display, EnumString};
+use std::ops::RangeInclusive;
+use strum::{Display, EnumString, VariantNames};
 use typed_builder::TypedBuilder;
+use url::Url;
 
 use crate::alert::AlertStatus;
 use crate::client::*;
 use crate::entity::{Entity, Id};
 use crate::error::Result;
+use crate::expression::Expression;
 use crate::role::RoleFullname;
 use crate::service::ServiceName;
 
+const MONITORS_UPDATE_CONCURRENCY: usize = 5;
+
 /// A monitor entity
 pub type Monitor = Entity<MonitorValue>;
 
@@ -21,7 +27,8 @@ pub type MonitorId = Id<MonitorValue>;
 
 /// A monitor value
 #[skip_serializing_none]
-#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, VariantNames)]
+#[strum(serialize_all = "camelCase")]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum MonitorValue {
     #[serde(rename_all = "camelCase")]
@@ -46,6 +53,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Connectivity {
@@ -65,6 +75,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Service {
@@ -89,6 +102,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     External {
@@ -126,6 +142,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Expression {
@@ -142,6 +161,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     AnomalyDetection {
@@ -162,6 +184,9 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
     #[serde(rename_all = "camelCase")]
     Query {
@@ -179,7 +204,17 @@ pub enum MonitorValue {
         notification_interval: Option<u64>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_mute: bool,
+        /// The fields not supported by this library, preserved on update.
+        #[serde(flatten)]
+        extra: serde_json::Map<String, serde_json::Value>,
     },
+    /// A monitor of the type not supported by this library.
+    /// The JSON object is preserved, including the `type` field.
+    #[serde(untagged)]
+    Unknown(
+        #[serde(deserialize_with = "crate::entity::deserialize_unknown::<MonitorValue, _>")]
+        serde_json::Map<String, serde_json::Value>,
+    ),
 }
 
 impl MonitorValue {
@@ -193,6 +228,11 @@ impl MonitorValue {
             Self::Expression { ref name, .. } => name.clone(),
             Self::AnomalyDetection { ref name, .. } => name.clone(),
             Self::Query { ref name, .. } => name.clone(),
+            Self::Unknown(ref fields) => fields
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
         }
     }
 
@@ -206,6 +246,11 @@ impl MonitorValue {
             Self::Expression { ref memo, .. } => memo.clone(),
             Self::AnomalyDetection { ref memo, .. } => memo.clone(),
             Self::Query { ref memo, .. } => memo.clone(),
+            Self::Unknown(ref fields) => fields
+                .get("memo")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
         }
     }
 
@@ -219,6 +264,703 @@ impl MonitorValue {
             Self::Expression { is_mute, .. } => is_mute,
             Self::AnomalyDetection { is_mute, .. } => is_mute,
             Self::Query { is_mute, .. } => is_mute,
+            Self::Unknown(ref fields) => fields
+                .get("isMute")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the type of the monitor, or `None` if the type is not supported by this library.
+    pub fn monitor_type(&self) -> Option<MonitorType> {
+        match *self {
+            Self::Host { .. } => Some(MonitorType::Host),
+            Self::Connectivity { .. } => Some(MonitorType::Connectivity),
+            Self::Service { .. } => Some(MonitorType::Service),
+            Self::External { .. } => Some(MonitorType::External),
+            Self::Expression { .. } => Some(MonitorType::Expression),
+            Self::AnomalyDetection { .. } => Some(MonitorType::AnomalyDetection),
+            Self::Query { .. } => Some(MonitorType::Query),
+            Self::Unknown(ref fields) => fields
+                .get("type")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|monitor_type| monitor_type.parse().ok()),
+        }
+    }
+
+    /// Returns the `notification_interval` of the monitor.
+    pub fn notification_interval(&self) -> Option<u64> {
+        match *self {
+            Self::Host {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::Connectivity {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::Service {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::External {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::Expression {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::AnomalyDetection {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::Query {
+                notification_interval,
+                ..
+            } => notification_interval,
+            Self::Unknown(ref fields) => fields
+                .get("notificationInterval")
+                .and_then(serde_json::Value::as_u64),
+        }
+    }
+
+    /// Returns the `operator` of the monitor, or `None` if the monitor has no operator.
+    pub fn operator(&self) -> Option<MonitorOperator> {
+        match *self {
+            Self::Host { operator, .. } => Some(operator),
+            Self::Service { operator, .. } => Some(operator),
+            Self::Expression { operator, .. } => Some(operator),
+            Self::Query { operator, .. } => Some(operator),
+            Self::Connectivity { .. } | Self::External { .. } | Self::AnomalyDetection { .. } => {
+                None
+            }
+            Self::Unknown(ref fields) => fields
+                .get("operator")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|operator| operator.parse().ok()),
+        }
+    }
+
+    /// Returns the `warning` and `critical` thresholds of the monitor.
+    /// The thresholds of the response time are returned for the external monitor.
+    pub fn thresholds(&self) -> (Option<f64>, Option<f64>) {
+        match *self {
+            Self::Host {
+                warning, critical, ..
+            } => (warning, critical),
+            Self::Service {
+                warning, critical, ..
+            } => (warning, critical),
+            Self::External {
+                response_time_warning,
+                response_time_critical,
+                ..
+            } => (response_time_warning, response_time_critical),
+            Self::Expression {
+                warning, critical, ..
+            } => (warning, critical),
+            Self::Query {
+                warning, critical, ..
+            } => (warning, critical),
+            Self::Connectivity { .. } | Self::AnomalyDetection { .. } => (None, None),
+            Self::Unknown(ref fields) => (
+                fields.get("warning").and_then(serde_json::Value::as_f64),
+                fields.get("critical").and_then(serde_json::Value::as_f64),
+            ),
+        }
+    }
+
+    /// Returns the `scopes` of the monitor.
+    pub fn scopes(&self) -> Vec<MonitorScope> {
+        match *self {
+            Self::Host { ref scopes, .. } => scopes.clone(),
+            Self::Connectivity { ref scopes, .. } => scopes.clone(),
+            Self::AnomalyDetection { ref scopes, .. } => scopes.clone(),
+            Self::Service { .. }
+            | Self::External { .. }
+            | Self::Expression { .. }
+            | Self::Query { .. } => Vec::new(),
+            Self::Unknown(ref fields) => fields
+                .get("scopes")
+                .and_then(|scopes| serde_json::from_value(scopes.clone()).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the `exclude_scopes` of the monitor.
+    pub fn exclude_scopes(&self) -> Vec<MonitorScope> {
+        match *self {
+            Self::Host {
+                ref exclude_scopes, ..
+            } => exclude_scopes.clone(),
+            Self::Connectivity {
+                ref exclude_scopes, ..
+            } => exclude_scopes.clone(),
+            Self::Service { .. }
+            | Self::External { .. }
+            | Self::Expression { .. }
+            | Self::AnomalyDetection { .. }
+            | Self::Query { .. } => Vec::new(),
+            Self::Unknown(ref fields) => fields
+                .get("excludeScopes")
+                .and_then(|scopes| serde_json::from_value(scopes.clone()).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Sets the `name` of the monitor.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        match *self {
+            Self::Host {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::Connectivity {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::Service {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::External {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::Expression {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::AnomalyDetection {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::Query {
+                name: ref mut n, ..
+            } => *n = name,
+            Self::Unknown(ref mut fields) => {
+                fields.insert("name".to_owned(), name.into());
+            }
+        }
+    }
+
+    /// Sets the `memo` of the monitor.
+    pub fn set_memo(&mut self, memo: impl Into<String>) {
+        let memo = memo.into();
+        match *self {
+            Self::Host {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::Connectivity {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::Service {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::External {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::Expression {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::AnomalyDetection {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::Query {
+                memo: ref mut m, ..
+            } => *m = memo,
+            Self::Unknown(ref mut fields) => {
+                fields.insert("memo".to_owned(), memo.into());
+            }
+        }
+    }
+
+    /// Sets the `is_mute` of the monitor.
+    pub fn set_mute(&mut self, is_mute: bool) {
+        match *self {
+            Self::Host {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::Connectivity {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::Service {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::External {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::Expression {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::AnomalyDetection {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::Query {
+                is_mute: ref mut m, ..
+            } => *m = is_mute,
+            Self::Unknown(ref mut fields) => {
+                fields.insert("isMute".to_owned(), is_mute.into());
+            }
+        }
+    }
+
+    /// Sets the `notification_interval` of the monitor.
+    pub fn set_notification_interval(&mut self, notification_interval: Option<u64>) {
+        match *self {
+            Self::Host {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::Connectivity {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::Service {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::External {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::Expression {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::AnomalyDetection {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::Query {
+                notification_interval: ref mut n,
+                ..
+            } => *n = notification_interval,
+            Self::Unknown(ref mut fields) => match notification_interval {
+                Some(notification_interval) => {
+                    fields.insert(
+                        "notificationInterval".to_owned(),
+                        notification_interval.into(),
+                    );
+                }
+                None => {
+                    fields.remove("notificationInterval");
+                }
+            },
+        }
+    }
+}
+
+impl MonitorValue {
+    /// Creates a builder of a host metric monitor.
+    /// ```rust
+    /// use mackerel_client::monitor::{MonitorOperator, MonitorValue};
+    ///
+    /// let monitor_value = MonitorValue::host()
+    ///     .name("Example host monitor")
+    ///     .duration(5)
+    ///     .metric("loadavg5")
+    ///     .operator(MonitorOperator::GreaterThan)
+    ///     .warning(10.0)
+    ///     .critical(20.0)
+    ///     .scopes(["service1"])
+    ///     .build();
+    /// assert_eq!(monitor_value.name(), "Example host monitor");
+    /// ```
+    pub fn host() -> HostMonitorBuilder {
+        HostMonitor::builder()
+    }
+
+    /// Creates a builder of a host connectivity monitor.
+    pub fn connectivity() -> ConnectivityMonitorBuilder {
+        ConnectivityMonitor::builder()
+    }
+
+    /// Creates a builder of a service metric monitor.
+    pub fn service() -> ServiceMonitorBuilder {
+        ServiceMonitor::builder()
+    }
+
+    /// Creates a builder of an external http monitor.
+    pub fn external() -> ExternalMonitorBuilder {
+        ExternalMonitor::builder()
+    }
+
+    /// Creates a builder of an expression monitor.
+    pub fn expression() -> ExpressionMonitorBuilder {
+        ExpressionMonitor::builder()
+    }
+
+    /// Creates a builder of an anomaly detection monitor.
+    pub fn anomaly_detection() -> AnomalyDetectionMonitorBuilder {
+        AnomalyDetectionMonitor::builder()
+    }
+
+    /// Creates a builder of a query monitor.
+    pub fn query() -> QueryMonitorBuilder {
+        QueryMonitor::builder()
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::Host`], created by [`MonitorValue::host`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct HostMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    #[builder(setter(!into))]
+    duration: u64,
+    metric: String,
+    operator: MonitorOperator,
+    #[builder(default, setter(strip_option))]
+    warning: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    critical: Option<f64>,
+    #[builder(default, setter(!into, strip_option))]
+    max_check_attempts: Option<u64>,
+    #[builder(
+        default,
+        setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+            .into_iter().map(Into::into).collect::<Vec<_>>()),
+    )]
+    scopes: Vec<MonitorScope>,
+    #[builder(
+        default,
+        setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+            .into_iter().map(Into::into).collect::<Vec<_>>()),
+    )]
+    exclude_scopes: Vec<MonitorScope>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<HostMonitor> for MonitorValue {
+    fn from(monitor: HostMonitor) -> Self {
+        Self::Host {
+            name: monitor.name,
+            memo: monitor.memo,
+            duration: monitor.duration,
+            metric: monitor.metric,
+            operator: monitor.operator,
+            warning: monitor.warning,
+            critical: monitor.critical,
+            max_check_attempts: monitor.max_check_attempts,
+            scopes: monitor.scopes,
+            exclude_scopes: monitor.exclude_scopes,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::Connectivity`], created by [`MonitorValue::connectivity`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct ConnectivityMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    #[builder(default = AlertStatus::Critical)]
+    alert_status_on_gone: AlertStatus,
+    #[builder(
+        default,
+        setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+            .into_iter().map(Into::into).collect::<Vec<_>>()),
+    )]
+    scopes: Vec<MonitorScope>,
+    #[builder(
+        default,
+        setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+            .into_iter().map(Into::into).collect::<Vec<_>>()),
+    )]
+    exclude_scopes: Vec<MonitorScope>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<ConnectivityMonitor> for MonitorValue {
+    fn from(monitor: ConnectivityMonitor) -> Self {
+        Self::Connectivity {
+            name: monitor.name,
+            memo: monitor.memo,
+            alert_status_on_gone: monitor.alert_status_on_gone,
+            scopes: monitor.scopes,
+            exclude_scopes: monitor.exclude_scopes,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::Service`], created by [`MonitorValue::service`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct ServiceMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    service: ServiceName,
+    #[builder(setter(!into))]
+    duration: u64,
+    metric: String,
+    operator: MonitorOperator,
+    #[builder(default, setter(strip_option))]
+    warning: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    critical: Option<f64>,
+    #[builder(default, setter(!into, strip_option))]
+    max_check_attempts: Option<u64>,
+    #[builder(default, setter(!into, strip_option))]
+    missing_duration_warning: Option<u64>,
+    #[builder(default, setter(!into, strip_option))]
+    missing_duration_critical: Option<u64>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<ServiceMonitor> for MonitorValue {
+    fn from(monitor: ServiceMonitor) -> Self {
+        Self::Service {
+            name: monitor.name,
+            memo: monitor.memo,
+            service: monitor.service,
+            duration: monitor.duration,
+            metric: monitor.metric,
+            operator: monitor.operator,
+            warning: monitor.warning,
+            critical: monitor.critical,
+            max_check_attempts: monitor.max_check_attempts,
+            missing_duration_warning: monitor.missing_duration_warning,
+            missing_duration_critical: monitor.missing_duration_critical,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::External`], created by [`MonitorValue::external`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct ExternalMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    url: String,
+    #[builder(default)]
+    method: ExternalMethod,
+    #[builder(
+        default,
+        setter(transform = |headers: impl IntoIterator<Item = ExternalHeader>| Some(headers
+            .into_iter().collect::<Vec<_>>())),
+    )]
+    headers: Option<Vec<ExternalHeader>>,
+    #[builder(default)]
+    request_body: String,
+    #[builder(default)]
+    follow_redirect: bool,
+    #[builder(default, setter(strip_option))]
+    service: Option<ServiceName>,
+    #[builder(default, setter(!into, strip_option))]
+    response_time_duration: Option<u64>,
+    #[builder(default, setter(strip_option))]
+    response_time_warning: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    response_time_critical: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    contains_string: Option<String>,
+    #[builder(default, setter(!into, strip_option))]
+    max_check_attempts: Option<u64>,
+    #[builder(default, setter(!into, strip_option))]
+    certification_expiration_warning: Option<u64>,
+    #[builder(default, setter(!into, strip_option))]
+    certification_expiration_critical: Option<u64>,
+    #[builder(default)]
+    skip_certificate_verification: bool,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<ExternalMonitor> for MonitorValue {
+    fn from(monitor: ExternalMonitor) -> Self {
+        Self::External {
+            name: monitor.name,
+            memo: monitor.memo,
+            url: monitor.url,
+            method: monitor.method,
+            headers: monitor.headers,
+            request_body: monitor.request_body,
+            follow_redirect: monitor.follow_redirect,
+            service: monitor.service,
+            response_time_duration: monitor.response_time_duration,
+            response_time_warning: monitor.response_time_warning,
+            response_time_critical: monitor.response_time_critical,
+            contains_string: monitor.contains_string,
+            max_check_attempts: monitor.max_check_attempts,
+            certification_expiration_warning: monitor.certification_expiration_warning,
+            certification_expiration_critical: monitor.certification_expiration_critical,
+            skip_certificate_verification: monitor.skip_certificate_verification,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::Expression`], created by [`MonitorValue::expression`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct ExpressionMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    expression: String,
+    operator: MonitorOperator,
+    #[builder(default, setter(strip_option))]
+    warning: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    critical: Option<f64>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<ExpressionMonitor> for MonitorValue {
+    fn from(monitor: ExpressionMonitor) -> Self {
+        Self::Expression {
+            name: monitor.name,
+            memo: monitor.memo,
+            expression: monitor.expression,
+            operator: monitor.operator,
+            warning: monitor.warning,
+            critical: monitor.critical,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::AnomalyDetection`], created by [`MonitorValue::anomaly_detection`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct AnomalyDetectionMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    #[builder(
+        default,
+        setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+            .into_iter().map(Into::into).collect::<Vec<_>>()),
+    )]
+    scopes: Vec<MonitorScope>,
+    #[builder(default, setter(strip_option))]
+    warning_sensitivity: Option<AnomalyDetectionSensitivity>,
+    #[builder(default, setter(strip_option))]
+    critical_sensitivity: Option<AnomalyDetectionSensitivity>,
+    #[builder(default, setter(!into, strip_option))]
+    max_check_attempts: Option<u64>,
+    #[builder(default, setter(strip_option))]
+    training_period_from: Option<DateTime<Utc>>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<AnomalyDetectionMonitor> for MonitorValue {
+    fn from(monitor: AnomalyDetectionMonitor) -> Self {
+        Self::AnomalyDetection {
+            name: monitor.name,
+            memo: monitor.memo,
+            scopes: monitor.scopes,
+            warning_sensitivity: monitor.warning_sensitivity,
+            critical_sensitivity: monitor.critical_sensitivity,
+            max_check_attempts: monitor.max_check_attempts,
+            training_period_from: monitor.training_period_from,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(
+    builder_type(vis = "pub", doc = "A builder of [`MonitorValue::Query`], created by [`MonitorValue::query`]."),
+    build_method(into = MonitorValue),
+    field_defaults(setter(into)),
+)]
+struct QueryMonitor {
+    name: String,
+    #[builder(default)]
+    memo: String,
+    query: String,
+    legend: String,
+    operator: MonitorOperator,
+    #[builder(default, setter(strip_option))]
+    warning: Option<f64>,
+    #[builder(default, setter(strip_option))]
+    critical: Option<f64>,
+    #[builder(default, setter(!into, strip_option))]
+    notification_interval: Option<u64>,
+    #[builder(default)]
+    is_mute: bool,
+    #[builder(default)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<QueryMonitor> for MonitorValue {
+    fn from(monitor: QueryMonitor) -> Self {
+        Self::Query {
+            name: monitor.name,
+            memo: monitor.memo,
+            query: monitor.query,
+            legend: monitor.legend,
+            operator: monitor.operator,
+            warning: monitor.warning,
+            critical: monitor.critical,
+            notification_interval: monitor.notification_interval,
+            is_mute: monitor.is_mute,
+            extra: monitor.extra,
         }
     }
 }
@@ -345,6 +1087,426 @@ pub enum AnomalyDetectionSensitivity {
     Sensitive,
 }
 
+/// A filter of the monitors to mute or unmute in bulk.
+/// A monitor matches the filter if it satisfies all the specified conditions,
+/// so the empty filter matches all the monitors.
+#[derive(PartialEq, Clone, Debug, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct MonitorFilter {
+    /// The monitor ids, any of which the monitor has.
+    #[builder(setter(transform = |monitor_ids: impl IntoIterator<Item = impl Into<MonitorId>>| monitor_ids
+        .into_iter().map(Into::into).collect::<Vec<_>>()))]
+    pub ids: Vec<MonitorId>,
+    /// The monitor types, any of which the monitor has.
+    #[builder(setter(transform = |monitor_types: impl IntoIterator<Item = MonitorType>| monitor_types
+        .into_iter().collect::<Vec<_>>()))]
+    pub monitor_types: Vec<MonitorType>,
+    /// The pattern of the monitor name, where `*` matches any characters.
+    #[builder(setter(strip_option))]
+    pub name_pattern: Option<String>,
+    /// The scopes, any of which overlaps with the scopes or the service of the monitor.
+    /// A service scope overlaps with the role scopes of the service.
+    #[builder(setter(transform = |scopes: impl IntoIterator<Item = impl Into<MonitorScope>>| scopes
+        .into_iter().map(Into::into).collect::<Vec<_>>()))]
+    pub scopes: Vec<MonitorScope>,
+}
+
+impl MonitorFilter {
+    /// Returns true if the monitor matches the filter.
+    pub fn matches(&self, monitor: &Monitor) -> bool {
+        (self.ids.is_empty() || self.ids.contains(&monitor.id))
+            && (self.monitor_types.is_empty()
+                || monitor
+                    .monitor_type()
+                    .is_some_and(|monitor_type| self.monitor_types.contains(&monitor_type)))
+            && self
+                .name_pattern
+                .as_ref()
+                .is_none_or(|name_pattern| match_name_pattern(name_pattern, &monitor.name()))
+            && (self.scopes.is_empty() || {
+                let monitor_scopes = monitor_scopes(monitor);
+                self.scopes.iter().any(|scope| {
+                    monitor_scopes
+                        .iter()
+                        .any(|monitor_scope| overlap_scopes(scope, monitor_scope))
+                })
+            })
+    }
+}
+
+pub(crate) fn match_name_pattern(name_pattern: &str, name: &str) -> bool {
+    let mut parts = name_pattern.split('*');
+    let Some(prefix) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(suffix) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(suffix)
+}
+
+fn monitor_scopes(monitor: &Monitor) -> Vec<MonitorScope> {
+    let mut scopes = monitor.scopes();
+    match monitor.value {
+        MonitorValue::Service { service, .. }
+        | MonitorValue::External {
+            service: Some(service),
+            ..
+        } => scopes.push(MonitorScope::Service(service)),
+        _ => {}
+    }
+    scopes
+}
+
+fn overlap_scopes(scope: &MonitorScope, other: &MonitorScope) -> bool {
+    match (scope, other) {
+        (MonitorScope::Service(service_name), MonitorScope::Role(role_fullname))
+        | (MonitorScope::Role(role_fullname), MonitorScope::Service(service_name)) => {
+            role_fullname.service_name == *service_name
+        }
+        _ => scope == other,
+    }
+}
+
+/// The result of muting or unmuting a monitor in bulk.
+#[derive(PartialEq, Debug)]
+pub struct MonitorMuteReport {
+    pub monitor_id: MonitorId,
+    pub name: String,
+    /// The status of the monitor, or the error on updating the monitor.
+    pub result: Result<MonitorMuteStatus>,
+}
+
+/// The status of a monitor muted or unmuted in bulk.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MonitorMuteStatus {
+    /// The monitor is updated.
+    Updated,
+    /// The monitor is not updated because it is already muted (or unmuted).
+    Unchanged,
+}
+
+/// A violation of the rules of the monitor definitions, found by [`MonitorValue::validate`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MonitorViolation {
+    /// The field path in the JSON representation, for example `headers[0].name`.
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for MonitorViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// The error of [`MonitorValue::validate`] with all the violations.
+#[derive(PartialEq, Eq, Clone, Debug, thiserror::Error)]
+#[error("invalid monitor: {}", .violations.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+pub struct MonitorValidationError {
+    pub violations: Vec<MonitorViolation>,
+}
+
+#[derive(Default)]
+struct MonitorValidator {
+    violations: Vec<MonitorViolation>,
+}
+
+impl MonitorValidator {
+    fn violation(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.violations.push(MonitorViolation {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    fn non_empty(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.violation(field, "must not be empty");
+        }
+    }
+
+    fn range(&mut self, field: &str, value_opt: Option<u64>, range: RangeInclusive<u64>) {
+        if let Some(value) = value_opt.filter(|value| !range.contains(value)) {
+            self.violation(
+                field,
+                format!(
+                    "must be between {} and {}, but got {}",
+                    range.start(),
+                    range.end(),
+                    value
+                ),
+            );
+        }
+    }
+
+    fn notification_interval(&mut self, notification_interval: Option<u64>) {
+        if let Some(notification_interval) =
+            notification_interval.filter(|&interval| interval < MIN_NOTIFICATION_INTERVAL)
+        {
+            self.violation(
+                "notificationInterval",
+                format!(
+                    "must be at least {}, but got {}",
+                    MIN_NOTIFICATION_INTERVAL, notification_interval
+                ),
+            );
+        }
+    }
+
+    fn thresholds(
+        &mut self,
+        operator: MonitorOperator,
+        warning: Option<f64>,
+        critical: Option<f64>,
+    ) {
+        self.threshold_values(("warning", "critical"), operator, warning, critical);
+        if warning.is_none() && critical.is_none() {
+            self.violation("critical", "either warning or critical must be specified");
+        }
+    }
+
+    fn threshold_values(
+        &mut self,
+        (warning_field, critical_field): (&str, &str),
+        operator: MonitorOperator,
+        warning: Option<f64>,
+        critical: Option<f64>,
+    ) {
+        for (field, threshold) in [(warning_field, warning), (critical_field, critical)] {
+            if threshold.is_some_and(|threshold| !threshold.is_finite()) {
+                self.violation(field, "must be a finite number");
+            }
+        }
+        if let (Some(warning), Some(critical)) = (warning, critical) {
+            match operator {
+                MonitorOperator::GreaterThan if warning > critical => self.violation(
+                    critical_field,
+                    format!(
+                        "must be greater than or equal to {} for the operator {}",
+                        warning_field, operator
+                    ),
+                ),
+                MonitorOperator::LessThan if warning < critical => self.violation(
+                    critical_field,
+                    format!(
+                        "must be less than or equal to {} for the operator {}",
+                        warning_field, operator
+                    ),
+                ),
+                _ => {}
+            }
+        }
+    }
+}
+
+const MIN_NOTIFICATION_INTERVAL: u64 = 10;
+const DURATION_RANGE: RangeInclusive<u64> = 1..=10;
+const MAX_CHECK_ATTEMPTS_RANGE: RangeInclusive<u64> = 1..=10;
+const MISSING_DURATION_RANGE: RangeInclusive<u64> = 10..=10080;
+
+impl MonitorValue {
+    /// Validates the monitor definition against the constraints of the fields
+    /// described in <https://mackerel.io/api-docs/entry/monitors>, and returns all the violations.
+    /// The expression of the expression monitor is validated by [`Expression::validate`].
+    /// The monitors of the type not supported by this library are not validated.
+    pub fn validate(&self) -> std::result::Result<(), MonitorValidationError> {
+        let mut validator = MonitorValidator::default();
+        if !matches!(self, Self::Unknown(_)) {
+            validator.non_empty("name", &self.name());
+            validator.notification_interval(self.notification_interval());
+        }
+        match *self {
+            Self::Host {
+                duration,
+                ref metric,
+                operator,
+                warning,
+                critical,
+                max_check_attempts,
+                ..
+            } => {
+                validator.range("duration", Some(duration), DURATION_RANGE);
+                validator.non_empty("metric", metric);
+                validator.thresholds(operator, warning, critical);
+                validator.range(
+                    "maxCheckAttempts",
+                    max_check_attempts,
+                    MAX_CHECK_ATTEMPTS_RANGE,
+                );
+            }
+            Self::Connectivity { .. } => {}
+            Self::Service {
+                duration,
+                ref metric,
+                operator,
+                warning,
+                critical,
+                max_check_attempts,
+                missing_duration_warning,
+                missing_duration_critical,
+                ..
+            } => {
+                validator.range("duration", Some(duration), DURATION_RANGE);
+                validator.non_empty("metric", metric);
+                validator.thresholds(operator, warning, critical);
+                validator.range(
+                    "maxCheckAttempts",
+                    max_check_attempts,
+                    MAX_CHECK_ATTEMPTS_RANGE,
+                );
+                validator.range(
+                    "missingDurationWarning",
+                    missing_duration_warning,
+                    MISSING_DURATION_RANGE,
+                );
+                validator.range(
+                    "missingDurationCritical",
+                    missing_duration_critical,
+                    MISSING_DURATION_RANGE,
+                );
+                if let (Some(warning), Some(critical)) =
+                    (missing_duration_warning, missing_duration_critical)
+                {
+                    if warning > critical {
+                        validator.violation(
+                            "missingDurationCritical",
+                            "must be greater than or equal to missingDurationWarning",
+                        );
+                    }
+                }
+            }
+            Self::External {
+                ref url,
+                ref headers,
+                response_time_duration,
+                response_time_warning,
+                response_time_critical,
+                max_check_attempts,
+                certification_expiration_warning,
+                certification_expiration_critical,
+                ..
+            } => {
+                match Url::parse(url) {
+                    Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                    Ok(url) => validator.violation(
+                        "url",
+                        format!("must be http or https, but got {}", url.scheme()),
+                    ),
+                    Err(err) => validator.violation("url", format!("invalid url: {}", err)),
+                }
+                for (i, header) in headers.iter().flatten().enumerate() {
+                    validator.non_empty(&format!("headers[{}].name", i), &header.name);
+                }
+                validator.range(
+                    "responseTimeDuration",
+                    response_time_duration,
+                    DURATION_RANGE,
+                );
+                validator.threshold_values(
+                    ("responseTimeWarning", "responseTimeCritical"),
+                    MonitorOperator::GreaterThan,
+                    response_time_warning,
+                    response_time_critical,
+                );
+                if response_time_duration.is_none()
+                    && (response_time_warning.is_some() || response_time_critical.is_some())
+                {
+                    validator.violation(
+                        "responseTimeDuration",
+                        "must be specified with the response time thresholds",
+                    );
+                }
+                validator.range(
+                    "maxCheckAttempts",
+                    max_check_attempts,
+                    MAX_CHECK_ATTEMPTS_RANGE,
+                );
+                if let (Some(warning), Some(critical)) = (
+                    certification_expiration_warning,
+                    certification_expiration_critical,
+                ) {
+                    if warning < critical {
+                        validator.violation(
+                            "certificationExpirationCritical",
+                            "must be less than or equal to certificationExpirationWarning",
+                        );
+                    }
+                }
+            }
+            Self::Expression {
+                ref expression,
+                operator,
+                warning,
+                critical,
+                ..
+            } => {
+                validator.non_empty("expression", expression);
+                if !expression.trim().is_empty() {
+                    let errors = match Expression::parse(expression) {
+                        Ok(expression) => expression.validate().err().unwrap_or_default(),
+                        Err(err) => vec![err],
+                    };
+                    for err in errors {
+                        validator.violation("expression", err.to_string());
+                    }
+                }
+                validator.thresholds(operator, warning, critical);
+            }
+            Self::AnomalyDetection {
+                ref scopes,
+                warning_sensitivity,
+                critical_sensitivity,
+                max_check_attempts,
+                ..
+            } => {
+                if scopes.is_empty() {
+                    validator.violation("scopes", "must not be empty");
+                }
+                if warning_sensitivity.is_none() && critical_sensitivity.is_none() {
+                    validator.violation(
+                        "criticalSensitivity",
+                        "either warningSensitivity or criticalSensitivity must be specified",
+                    );
+                }
+                validator.range(
+                    "maxCheckAttempts",
+                    max_check_attempts,
+                    MAX_CHECK_ATTEMPTS_RANGE,
+                );
+            }
+            Self::Query {
+                ref query,
+                operator,
+                warning,
+                critical,
+                ..
+            } => {
+                validator.non_empty("query", query);
+                validator.thresholds(operator, warning, critical);
+            }
+            Self::Unknown(_) => {}
+        }
+        if validator.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(MonitorValidationError {
+                violations: validator.violations,
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,6 +1529,7 @@ mod tests {
                 exclude_scopes: vec!["service0:role3".into()],
                 notification_interval: Some(30),
                 is_mute: false,
+                extra: Default::default(),
             })
             .build()
     }
@@ -400,6 +1563,7 @@ mod tests {
                 exclude_scopes: vec![],
                 notification_interval: None,
                 is_mute: false,
+                extra: Default::default(),
             })
             .build()
     }
@@ -431,6 +1595,7 @@ mod tests {
                 missing_duration_critical: Some(120),
                 notification_interval: Some(30),
                 is_mute: false,
+                extra: Default::default(),
             })
             .build()
     }
@@ -479,6 +1644,7 @@ mod tests {
                 skip_certificate_verification: true,
                 notification_interval: Some(60),
                 is_mute: true,
+                extra: Default::default(),
             })
             .build()
     }
@@ -520,6 +1686,7 @@ mod tests {
                 critical: None,
                 notification_interval: None,
                 is_mute: true,
+                extra: Default::default(),
             })
             .build()
     }
@@ -550,6 +1717,7 @@ mod tests {
                 training_period_from: Some(DateTime::from_timestamp(1580000000, 0).unwrap()),
                 notification_interval: None,
                 is_mute: true,
+                extra: Default::default(),
             })
             .build()
     }
@@ -582,6 +1750,7 @@ mod tests {
                 critical: Some(90.0),
                 notification_interval: None,
                 is_mute: false,
+                extra: Default::default(),
             })
             .build()
     }
@@ -600,6 +1769,64 @@ mod tests {
         })
     }
 
+    fn connectivity_monitor_with_extra_example() -> Monitor {
+        Monitor::builder()
+            .id("monitor8")
+            .value(MonitorValue::Connectivity {
+                name: "Example connectivity monitor".to_string(),
+                memo: "Monitor memo".to_string(),
+                alert_status_on_gone: AlertStatus::Critical,
+                scopes: vec![],
+                exclude_scopes: vec![],
+                notification_interval: None,
+                is_mute: false,
+                extra: json!({ "newSetting": { "key": "value" } })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            })
+            .build()
+    }
+
+    fn connectivity_monitor_with_extra_json_example() -> serde_json::Value {
+        json!({
+            "type": "connectivity",
+            "id": "monitor8",
+            "name": "Example connectivity monitor",
+            "memo": "Monitor memo",
+            "newSetting": { "key": "value" },
+        })
+    }
+
+    fn unknown_monitor_example() -> Monitor {
+        Monitor::builder()
+            .id("monitor9")
+            .value(MonitorValue::Unknown(
+                json!({
+                    "type": "newMonitorType",
+                    "name": "Example unknown monitor",
+                    "memo": "Monitor memo",
+                    "isMute": true,
+                    "newSetting": { "key": "value" },
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            ))
+            .build()
+    }
+
+    fn unknown_monitor_json_example() -> serde_json::Value {
+        json!({
+            "type": "newMonitorType",
+            "id": "monitor9",
+            "name": "Example unknown monitor",
+            "memo": "Monitor memo",
+            "isMute": true,
+            "newSetting": { "key": "value" },
+        })
+    }
+
     #[rstest]
     #[case(host_monitor_example(), host_monitor_json_example())]
     #[case(connectivity_monitor_example(), connectivity_monitor_json_example())]
@@ -611,11 +1838,308 @@ mod tests {
         anomaly_detection_monitor_json_example()
     )]
     #[case(query_monitor_example(), query_monitor_json_example())]
+    #[case(
+        connectivity_monitor_with_extra_example(),
+        connectivity_monitor_with_extra_json_example()
+    )]
+    #[case(unknown_monitor_example(), unknown_monitor_json_example())]
     fn test_monitor_json(#[case] monitor: Monitor, #[case] json: serde_json::Value) {
         assert_eq!(serde_json::to_value(&monitor).unwrap(), json);
         assert_eq!(monitor, serde_json::from_value(json).unwrap());
     }
 
+    #[rstest]
+    #[case(json!({ "type": "host", "name": "Example host monitor", "duration": 5 }))]
+    #[case(json!({ "type": "connectivity", "name": 1 }))]
+    #[case(json!({ "type": "query", "name": "Example query monitor" }))]
+    #[case(json!({ "name": "Example monitor without type" }))]
+    fn test_monitor_json_malformed(#[case] json: serde_json::Value) {
+        assert!(serde_json::from_value::<MonitorValue>(json).is_err());
+    }
+
+    #[test]
+    fn test_monitor_variant_names() {
+        assert_eq!(
+            MonitorValue::VARIANTS,
+            [
+                "host",
+                "connectivity",
+                "service",
+                "external",
+                "expression",
+                "anomalyDetection",
+                "query",
+                "unknown",
+            ],
+        );
+    }
+
+    #[rstest]
+    #[case(
+        MonitorValue::host()
+            .name("Example host monitor")
+            .memo("Monitor memo")
+            .duration(5)
+            .metric("custom.foo.bar")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .critical(20.0)
+            .max_check_attempts(5)
+            .scopes(["service0"])
+            .exclude_scopes(["service0:role3"])
+            .notification_interval(30)
+            .build(),
+        host_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::host()
+            .name("Example host monitor")
+            .duration(5)
+            .metric("loadavg5")
+            .operator(MonitorOperator::GreaterThan)
+            .build(),
+        MonitorValue::Host {
+            name: "Example host monitor".to_string(),
+            memo: "".to_string(),
+            duration: 5,
+            metric: "loadavg5".to_string(),
+            operator: MonitorOperator::GreaterThan,
+            warning: None,
+            critical: None,
+            max_check_attempts: None,
+            scopes: vec![],
+            exclude_scopes: vec![],
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .memo("Monitor memo")
+            .alert_status_on_gone(AlertStatus::Warning)
+            .build(),
+        connectivity_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .build(),
+        MonitorValue::Connectivity {
+            name: "Example connectivity monitor".to_string(),
+            memo: "".to_string(),
+            alert_status_on_gone: AlertStatus::Critical,
+            scopes: vec![],
+            exclude_scopes: vec![],
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .memo("Monitor memo")
+            .extra(
+                json!({ "newSetting": { "key": "value" } })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            )
+            .build(),
+        connectivity_monitor_with_extra_example().value,
+    )]
+    #[case(
+        MonitorValue::service()
+            .name("Example service monitor")
+            .memo("Monitor memo")
+            .service("service1")
+            .duration(5)
+            .metric("custom.service.count")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(100.0)
+            .critical(200.0)
+            .max_check_attempts(10)
+            .missing_duration_warning(60)
+            .missing_duration_critical(120)
+            .notification_interval(30)
+            .build(),
+        service_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::service()
+            .name("Example service monitor")
+            .service("service1")
+            .duration(5)
+            .metric("custom.service.count")
+            .operator(MonitorOperator::LessThan)
+            .build(),
+        MonitorValue::Service {
+            name: "Example service monitor".to_string(),
+            memo: "".to_string(),
+            service: "service1".into(),
+            duration: 5,
+            metric: "custom.service.count".to_string(),
+            operator: MonitorOperator::LessThan,
+            warning: None,
+            critical: None,
+            max_check_attempts: None,
+            missing_duration_warning: None,
+            missing_duration_critical: None,
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::external()
+            .name("Example external monitor")
+            .memo("Monitor memo")
+            .url("https://example.com")
+            .method(ExternalMethod::Get)
+            .headers([ExternalHeader::builder()
+                .name("Cache-Control")
+                .value("no-cache")
+                .build()])
+            .request_body("Request Body")
+            .follow_redirect(true)
+            .service("service1")
+            .response_time_duration(5)
+            .response_time_warning(3000.0)
+            .response_time_critical(5000.0)
+            .contains_string("Example Domain")
+            .max_check_attempts(5)
+            .certification_expiration_warning(1200)
+            .certification_expiration_critical(60)
+            .skip_certificate_verification(true)
+            .notification_interval(60)
+            .is_mute(true)
+            .build(),
+        external_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::external()
+            .name("Example external monitor")
+            .url("https://example.com")
+            .build(),
+        MonitorValue::External {
+            name: "Example external monitor".to_string(),
+            memo: "".to_string(),
+            url: "https://example.com".to_string(),
+            method: ExternalMethod::Get,
+            headers: None,
+            request_body: "".to_string(),
+            follow_redirect: false,
+            service: None,
+            response_time_duration: None,
+            response_time_warning: None,
+            response_time_critical: None,
+            contains_string: None,
+            max_check_attempts: None,
+            certification_expiration_warning: None,
+            certification_expiration_critical: None,
+            skip_certificate_verification: false,
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .memo("Monitor memo")
+            .expression("min(role(\"foo:bar\", \"custom.foo.bar\"))")
+            .operator(MonitorOperator::LessThan)
+            .warning(10.0)
+            .is_mute(true)
+            .build(),
+        expression_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("max(role(\"foo:bar\", \"custom.foo.bar\"))")
+            .operator(MonitorOperator::GreaterThan)
+            .build(),
+        MonitorValue::Expression {
+            name: "Example expression monitor".to_string(),
+            memo: "".to_string(),
+            expression: "max(role(\"foo:bar\", \"custom.foo.bar\"))".to_string(),
+            operator: MonitorOperator::GreaterThan,
+            warning: None,
+            critical: None,
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::anomaly_detection()
+            .name("Example anomaly detection monitor")
+            .memo("Monitor memo")
+            .scopes(["service0:role0"])
+            .warning_sensitivity(AnomalyDetectionSensitivity::Normal)
+            .critical_sensitivity(AnomalyDetectionSensitivity::Insensitive)
+            .max_check_attempts(3)
+            .training_period_from(DateTime::from_timestamp(1580000000, 0).unwrap())
+            .is_mute(true)
+            .build(),
+        anomaly_detection_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::anomaly_detection()
+            .name("Example anomaly detection monitor")
+            .build(),
+        MonitorValue::AnomalyDetection {
+            name: "Example anomaly detection monitor".to_string(),
+            memo: "".to_string(),
+            scopes: vec![],
+            warning_sensitivity: None,
+            critical_sensitivity: None,
+            max_check_attempts: None,
+            training_period_from: None,
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    #[case(
+        MonitorValue::query()
+            .name("Example query monitor")
+            .memo("Monitor memo")
+            .query("container.cpu.utilization{label=\"value\"}")
+            .legend("cpu.utilization {{k8s.node.name}}")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(75.0)
+            .critical(90.0)
+            .build(),
+        query_monitor_example().value,
+    )]
+    #[case(
+        MonitorValue::query()
+            .name("Example query monitor")
+            .query("container.cpu.utilization")
+            .legend("")
+            .operator(MonitorOperator::GreaterThan)
+            .build(),
+        MonitorValue::Query {
+            name: "Example query monitor".to_string(),
+            memo: "".to_string(),
+            query: "container.cpu.utilization".to_string(),
+            legend: "".to_string(),
+            operator: MonitorOperator::GreaterThan,
+            warning: None,
+            critical: None,
+            notification_interval: None,
+            is_mute: false,
+            extra: Default::default(),
+        },
+    )]
+    fn test_monitor_builder(#[case] monitor_value: MonitorValue, #[case] expected: MonitorValue) {
+        assert_eq!(monitor_value, expected);
+    }
+
     #[rstest]
     #[case(host_monitor_example(), "Example host monitor")]
     #[case(connectivity_monitor_example(), "Example connectivity monitor")]
@@ -627,6 +2151,7 @@ mod tests {
         "Example anomaly detection monitor"
     )]
     #[case(query_monitor_example(), "Example query monitor")]
+    #[case(unknown_monitor_example(), "Example unknown monitor")]
     fn test_monitor_name(#[case] monitor: Monitor, #[case] name_str: &str) {
         assert_eq!(monitor.name(), name_str);
     }
@@ -639,6 +2164,7 @@ mod tests {
     #[case(expression_monitor_example())]
     #[case(anomaly_detection_monitor_example())]
     #[case(query_monitor_example())]
+    #[case(unknown_monitor_example())]
     fn test_monitor_memo(#[case] monitor: Monitor) {
         assert_eq!(monitor.memo(), "Monitor memo");
     }
@@ -651,10 +2177,89 @@ mod tests {
     #[case(expression_monitor_example(), true)]
     #[case(anomaly_detection_monitor_example(), true)]
     #[case(query_monitor_example(), false)]
+    #[case(unknown_monitor_example(), true)]
     fn test_monitor_is_mute(#[case] monitor: Monitor, #[case] is_mute: bool) {
         assert_eq!(monitor.is_mute(), is_mute);
     }
 
+    #[rstest]
+    #[case(host_monitor_example(), Some(MonitorType::Host))]
+    #[case(connectivity_monitor_example(), Some(MonitorType::Connectivity))]
+    #[case(service_monitor_example(), Some(MonitorType::Service))]
+    #[case(external_monitor_example(), Some(MonitorType::External))]
+    #[case(expression_monitor_example(), Some(MonitorType::Expression))]
+    #[case(
+        anomaly_detection_monitor_example(),
+        Some(MonitorType::AnomalyDetection)
+    )]
+    #[case(query_monitor_example(), Some(MonitorType::Query))]
+    #[case(unknown_monitor_example(), None)]
+    fn test_monitor_type_of_value(
+        #[case] monitor: Monitor,
+        #[case] monitor_type: Option<MonitorType>,
+    ) {
+        assert_eq!(monitor.monitor_type(), monitor_type);
+    }
+
+    #[rstest]
+    #[case(host_monitor_example(), (Some(10.0), Some(20.0)))]
+    #[case(connectivity_monitor_example(), (None, None))]
+    #[case(service_monitor_example(), (Some(100.0), Some(200.0)))]
+    #[case(external_monitor_example(), (Some(3000.0), Some(5000.0)))]
+    #[case(expression_monitor_example(), (Some(10.0), None))]
+    #[case(anomaly_detection_monitor_example(), (None, None))]
+    #[case(query_monitor_example(), (Some(75.0), Some(90.0)))]
+    #[case(unknown_monitor_example(), (None, None))]
+    fn test_monitor_thresholds(
+        #[case] monitor: Monitor,
+        #[case] thresholds: (Option<f64>, Option<f64>),
+    ) {
+        assert_eq!(monitor.thresholds(), thresholds);
+    }
+
+    #[rstest]
+    #[case(host_monitor_example(), vec!["service0".into()], vec!["service0:role3".into()])]
+    #[case(connectivity_monitor_example(), vec![], vec![])]
+    #[case(service_monitor_example(), vec![], vec![])]
+    #[case(anomaly_detection_monitor_example(), vec!["service0:role0".into()], vec![])]
+    #[case(unknown_monitor_example(), vec![], vec![])]
+    fn test_monitor_scopes(
+        #[case] monitor: Monitor,
+        #[case] scopes: Vec<MonitorScope>,
+        #[case] exclude_scopes: Vec<MonitorScope>,
+    ) {
+        assert_eq!(monitor.scopes(), scopes);
+        assert_eq!(monitor.exclude_scopes(), exclude_scopes);
+    }
+
+    #[rstest]
+    #[case(host_monitor_example())]
+    #[case(connectivity_monitor_example())]
+    #[case(service_monitor_example())]
+    #[case(external_monitor_example())]
+    #[case(expression_monitor_example())]
+    #[case(anomaly_detection_monitor_example())]
+    #[case(query_monitor_example())]
+    #[case(unknown_monitor_example())]
+    fn test_monitor_setters(#[case] mut monitor: Monitor) {
+        monitor.value.set_name("Updated monitor");
+        monitor.value.set_memo("Updated memo");
+        monitor.value.set_mute(true);
+        monitor.value.set_notification_interval(Some(120));
+        assert_eq!(monitor.name(), "Updated monitor");
+        assert_eq!(monitor.memo(), "Updated memo");
+        assert!(monitor.is_mute());
+        assert_eq!(monitor.notification_interval(), Some(120));
+        let json = serde_json::to_value(&monitor).unwrap();
+        assert_eq!(json["name"], "Updated monitor");
+        assert_eq!(json["isMute"], true);
+        assert_eq!(json["notificationInterval"], 120);
+        monitor.value.set_mute(false);
+        monitor.value.set_notification_interval(None);
+        assert!(!monitor.is_mute());
+        assert_eq!(monitor.notification_interval(), None);
+    }
+
     #[rstest]
     #[case(MonitorType::Connectivity, "connectivity")]
     #[case(MonitorType::Host, "host")]
@@ -771,91 +2376,407 @@ mod tests {
             anomaly_detection_sensitivity_str
         );
     }
+
+    #[rstest]
+    #[case(MonitorFilter::default(), true)]
+    #[case(MonitorFilter::builder().ids(["monitor1", "monitor2"]).build(), true)]
+    #[case(MonitorFilter::builder().ids(["monitor2"]).build(), false)]
+    #[case(MonitorFilter::builder().monitor_types([MonitorType::Host]).build(), true)]
+    #[case(MonitorFilter::builder().monitor_types([MonitorType::Service]).build(), false)]
+    #[case(MonitorFilter::builder().name_pattern("Example host monitor").build(), true)]
+    #[case(MonitorFilter::builder().name_pattern("Example *").build(), true)]
+    #[case(MonitorFilter::builder().name_pattern("*host*").build(), true)]
+    #[case(MonitorFilter::builder().name_pattern("*e*m*r").build(), true)]
+    #[case(MonitorFilter::builder().name_pattern("*").build(), true)]
+    #[case(MonitorFilter::builder().name_pattern("Example").build(), false)]
+    #[case(MonitorFilter::builder().name_pattern("*service*").build(), false)]
+    #[case(MonitorFilter::builder().name_pattern("Example*monitor*host").build(), false)]
+    #[case(MonitorFilter::builder().scopes(["service0"]).build(), true)]
+    #[case(MonitorFilter::builder().scopes(["service0:role1"]).build(), true)]
+    #[case(MonitorFilter::builder().scopes(["service1", "service0:role0"]).build(), true)]
+    #[case(MonitorFilter::builder().scopes(["service1"]).build(), false)]
+    #[case(
+        MonitorFilter::builder()
+            .ids(["monitor1"])
+            .monitor_types([MonitorType::Host])
+            .name_pattern("Example*")
+            .scopes(["service0"])
+            .build(),
+        true
+    )]
+    #[case(
+        MonitorFilter::builder()
+            .monitor_types([MonitorType::Host])
+            .scopes(["service1"])
+            .build(),
+        false
+    )]
+    fn test_monitor_filter(#[case] filter: MonitorFilter, #[case] matches: bool) {
+        assert_eq!(filter.matches(&host_monitor_example()), matches);
+    }
+
+    #[rstest]
+    #[case(service_monitor_example(), MonitorFilter::builder().scopes(["service1"]).build(), true)]
+    #[case(service_monitor_example(), MonitorFilter::builder().scopes(["service1:role1"]).build(), true)]
+    #[case(service_monitor_example(), MonitorFilter::builder().scopes(["service0"]).build(), false)]
+    #[case(external_monitor_example(), MonitorFilter::builder().scopes(["service1"]).build(), true)]
+    #[case(expression_monitor_example(), MonitorFilter::builder().scopes(["foo"]).build(), false)]
+    #[case(unknown_monitor_example(), MonitorFilter::builder().monitor_types([MonitorType::Host]).build(), false)]
+    #[case(unknown_monitor_example(), MonitorFilter::builder().name_pattern("*unknown*").build(), true)]
+    fn test_monitor_filter_of_type(
+        #[case] monitor: Monitor,
+        #[case] filter: MonitorFilter,
+        #[case] matches: bool,
+    ) {
+        assert_eq!(filter.matches(&monitor), matches);
+    }
+
+    #[rstest]
+    #[case(host_monitor_example(), vec![])]
+    #[case(connectivity_monitor_example(), vec![])]
+    #[case(service_monitor_example(), vec![])]
+    #[case(external_monitor_example(), vec![])]
+    #[case(expression_monitor_example(), vec![])]
+    #[case(anomaly_detection_monitor_example(), vec![])]
+    #[case(query_monitor_example(), vec![])]
+    #[case(unknown_monitor_example(), vec![])]
+    fn test_monitor_validate_examples(
+        #[case] monitor: Monitor,
+        #[case] violations: Vec<(&str, &str)>,
+    ) {
+        assert_eq!(
+            monitor.validate().map_err(|err| err.violations),
+            if violations.is_empty() {
+                Ok(())
+            } else {
+                Err(violations
+                    .into_iter()
+                    .map(|(field, message)| MonitorViolation {
+                        field: field.to_owned(),
+                        message: message.to_owned(),
+                    })
+                    .collect())
+            },
+        );
+    }
+
+    #[test]
+    fn test_monitor_validate_host() {
+        let monitor_value = MonitorValue::host()
+            .name(" ")
+            .duration(0)
+            .metric("loadavg5")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(20.0)
+            .critical(10.0)
+            .max_check_attempts(11)
+            .notification_interval(5)
+            .build();
+        let err = monitor_value.validate().unwrap_err();
+        assert_eq!(
+            err.violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "name: must not be empty",
+                "notificationInterval: must be at least 10, but got 5",
+                "duration: must be between 1 and 10, but got 0",
+                "critical: must be greater than or equal to warning for the operator >",
+                "maxCheckAttempts: must be between 1 and 10, but got 11",
+            ],
+        );
+        assert!(err
+            .to_string()
+            .starts_with("invalid monitor: name: must not be empty, "));
+    }
+
+    #[rstest]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("")
+            .operator(MonitorOperator::LessThan)
+            .warning(10.0)
+            .critical(20.0)
+            .build(),
+        vec![
+            "expression: must not be empty",
+            "critical: must be less than or equal to warning for the operator <",
+        ]
+    )]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("max(role('service', 'loadavg5')")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .build(),
+        vec!["expression: expected , or ) at position 31"]
+    )]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("max(role('service', 'loadavg5'))")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .build(),
+        vec!["expression: invalid role fullname \"service\" at position 9"]
+    )]
+    #[case(
+        MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("foo(max(role('service', 'loadavg5')))")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .build(),
+        vec!["expression: invalid role fullname \"service\" at position 13"]
+    )]
+    #[case(
+        MonitorValue::query()
+            .name("Example query monitor")
+            .query("container.cpu.utilization")
+            .legend("")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(f64::NAN)
+            .build(),
+        vec!["warning: must be a finite number"]
+    )]
+    #[case(
+        MonitorValue::query()
+            .name("Example query monitor")
+            .query("container.cpu.utilization")
+            .legend("")
+            .operator(MonitorOperator::GreaterThan)
+            .build(),
+        vec!["critical: either warning or critical must be specified"]
+    )]
+    #[case(
+        MonitorValue::service()
+            .name("Example service monitor")
+            .service("service1")
+            .duration(11)
+            .metric("custom.service.count")
+            .operator(MonitorOperator::GreaterThan)
+            .critical(200.0)
+            .missing_duration_warning(120)
+            .missing_duration_critical(5)
+            .build(),
+        vec![
+            "duration: must be between 1 and 10, but got 11",
+            "missingDurationCritical: must be between 10 and 10080, but got 5",
+            "missingDurationCritical: must be greater than or equal to missingDurationWarning",
+        ]
+    )]
+    #[case(
+        MonitorValue::external()
+            .name("Example external monitor")
+            .url("ftp://example.com")
+            .method(ExternalMethod::Post)
+            .request_body("Request Body")
+            .headers([ExternalHeader::builder().name("").value("no-cache").build()])
+            .response_time_warning(5000.0)
+            .response_time_critical(3000.0)
+            .certification_expiration_warning(30)
+            .certification_expiration_critical(60)
+            .build(),
+        vec![
+            "url: must be http or https, but got ftp",
+            "headers[0].name: must not be empty",
+            "responseTimeCritical: must be greater than or equal to responseTimeWarning for the operator >",
+            "responseTimeDuration: must be specified with the response time thresholds",
+            "certificationExpirationCritical: must be less than or equal to certificationExpirationWarning",
+        ]
+    )]
+    #[case(
+        MonitorValue::external()
+            .name("Example external monitor")
+            .url("example.com")
+            .build(),
+        vec!["url: invalid url: relative URL without a base"]
+    )]
+    #[case(
+        MonitorValue::anomaly_detection()
+            .name("Example anomaly detection monitor")
+            .max_check_attempts(0)
+            .build(),
+        vec![
+            "scopes: must not be empty",
+            "criticalSensitivity: either warningSensitivity or criticalSensitivity must be specified",
+            "maxCheckAttempts: must be between 1 and 10, but got 0",
+        ]
+    )]
+    fn test_monitor_validate(#[case] monitor_value: MonitorValue, #[case] violations: Vec<&str>) {
+        assert_eq!(
+            monitor_value
+                .validate()
+                .unwrap_err()
+                .violations
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            violations,
+        );
+    }
+
+    #[test]
+    fn test_monitor_validate_unknown_function() {
+        let monitor_value = MonitorValue::expression()
+            .name("Example expression monitor")
+            .expression("foo(max(role('service:role', 'loadavg5')), 'bar')")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .build();
+        assert_eq!(monitor_value.validate(), Ok(()));
+    }
+}
+
+client_methods! {
+    impl Client {
+        /// Fetches all the monitors.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_monitors(&self) -> Result<Vec<Monitor>> {
+            self.request(
+                Method::GET,
+                "/api/v0/monitors",
+                query_params![],
+                request_body![],
+                response_body! { monitors: Vec<Monitor> },
+            )
+            .await
+        }
+
+        /// Creates a new monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_monitor(
+            &self,
+            monitor_value: impl Borrow<MonitorValue>,
+        ) -> Result<Monitor> {
+            self.request(
+                Method::POST,
+                "/api/v0/monitors",
+                query_params![],
+                request_body!(monitor_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Gets a monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(monitor_id)))]
+        pub async fn get_monitor(&self, monitor_id: impl Into<MonitorId>) -> Result<Monitor> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/monitors/{}", monitor_id),
+                query_params![],
+                request_body![],
+                response_body! { monitor: Monitor },
+            )
+            .await
+        }
+
+        /// Updates a monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#update>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(monitor_id)))]
+        pub async fn update_monitor(
+            &self,
+            monitor_id: impl Into<MonitorId>,
+            monitor_value: impl Borrow<MonitorValue>,
+        ) -> Result<Monitor> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/monitors/{}", monitor_id),
+                query_params![],
+                request_body!(monitor_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Deletes a monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#delete>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(monitor_id)))]
+        pub async fn delete_monitor(&self, monitor_id: impl Into<MonitorId>) -> Result<Monitor> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/monitors/{}", monitor_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
+
+        /// Mutes the monitors matching the filter.
+        /// The monitors are updated concurrently, and the results are reported for each monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#update>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn mute_monitors(
+            &self,
+            filter: impl Borrow<MonitorFilter>,
+        ) -> Result<Vec<MonitorMuteReport>> {
+            self.update_monitors_mute(filter.borrow(), true).await
+        }
+
+        /// Unmutes the monitors matching the filter.
+        /// The monitors are updated concurrently, and the results are reported for each monitor.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#update>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn unmute_monitors(
+            &self,
+            filter: impl Borrow<MonitorFilter>,
+        ) -> Result<Vec<MonitorMuteReport>> {
+            self.update_monitors_mute(filter.borrow(), false).await
+        }
+    }
 }
 
 impl Client {
-    /// Fetches all the monitors.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/monitors#get>.
-    pub async fn list_monitors(&self) -> Result<Vec<Monitor>> {
-        self.request(
-            Method::GET,
-            "/api/v0/monitors",
-            query_params![],
-            request_body![],
-            response_body! { monitors: Vec<Monitor> },
-        )
-        .await
-    }
-
-    /// Creates a new monitor.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/monitors#create>.
-    pub async fn create_monitor(
+    async fn update_monitors_mute(
         &self,
-        monitor_value: impl Borrow<MonitorValue>,
-    ) -> Result<Monitor> {
-        self.request(
-            Method::POST,
-            "/api/v0/monitors",
-            query_params![],
-            request_body!(monitor_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Gets a monitor.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/monitors#get>.
-    pub async fn get_monitor(&self, monitor_id: impl Into<MonitorId>) -> Result<Monitor> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/monitors/{}", monitor_id),
-            query_params![],
-            request_body![],
-            response_body! { monitor: Monitor },
-        )
-        .await
-    }
-
-    /// Updates a monitor.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/monitors#update>.
-    pub async fn update_monitor(
-        &self,
-        monitor_id: impl Into<MonitorId>,
-        monitor_value: impl Borrow<MonitorValue>,
-    ) -> Result<Monitor> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/monitors/{}", monitor_id),
-            query_params![],
-            request_body!(monitor_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
-
-    /// Deletes a monitor.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/monitors#delete>.
-    pub async fn delete_monitor(&self, monitor_id: impl Into<MonitorId>) -> Result<Monitor> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/monitors/{}", monitor_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        filter: &MonitorFilter,
+        is_mute: bool,
+    ) -> Result<Vec<MonitorMuteReport>> {
+        let monitors = self.list_monitors().await?;
+        Ok(stream::iter(monitors)
+            .filter(|monitor| future::ready(filter.matches(monitor)))
+            .map(|mut monitor| async move {
+                let result = if monitor.is_mute() == is_mute {
+                    Ok(MonitorMuteStatus::Unchanged)
+                } else {
+                    monitor.value.set_mute(is_mute);
+                    self.update_monitor(monitor.id, &monitor.value)
+                        .await
+                        .map(|_| MonitorMuteStatus::Updated)
+                };
+                MonitorMuteReport {
+                    monitor_id: monitor.id,
+                    name: monitor.value.name(),
+                    result,
+                }
+            })
+            .buffered(MONITORS_UPDATE_CONCURRENCY)
+            .collect()
+            .await)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
+    use httptest::{all_of, matchers::*, responders::*, Expectation};
     use serde_json::json;
 
+    use crate::error::Error;
     use crate::monitor::*;
     use crate::tests::*;
 
@@ -868,6 +2789,7 @@ mod client_tests {
             exclude_scopes: vec![],
             notification_interval: None,
             is_mute: false,
+            extra: Default::default(),
         }
     }
 
@@ -984,4 +2906,92 @@ mod client_tests {
             Ok(entity_example()),
         );
     }
+
+    #[async_std::test]
+    async fn mute_monitors() {
+        let monitor_json = |id: &str, name: &str, scope: &str, is_mute: bool| {
+            json!({
+                "type": "connectivity",
+                "id": id,
+                "name": name,
+                "scopes": [scope],
+                "isMute": is_mute,
+            })
+        };
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/monitors",
+            response = json!({
+                "monitors": [
+                    monitor_json("monitor1", "Monitor 1", "service1", false),
+                    monitor_json("monitor2", "Monitor 2", "service1:role1", true),
+                    monitor_json("monitor3", "Monitor 3", "service2", false),
+                    monitor_json("monitor4", "Monitor 4", "service1:role2", false),
+                ],
+            }),
+        };
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("PUT", "/api/v0/monitors/monitor1"),
+                request::body(json_decoded(eq(json!({
+                    "type": "connectivity",
+                    "name": "Monitor 1",
+                    "scopes": ["service1"],
+                    "isMute": true,
+                })))),
+            ])
+            .respond_with(json_encoded(monitor_json(
+                "monitor1",
+                "Monitor 1",
+                "service1",
+                true,
+            ))),
+        );
+        server.expect(
+            Expectation::matching(request::method_path("PUT", "/api/v0/monitors/monitor4"))
+                .respond_with(
+                    status_code(404)
+                        .body(json!({ "error": { "message": "Monitor not found" } }).to_string()),
+                ),
+        );
+        let reports = test_client!(server)
+            .mute_monitors(MonitorFilter::builder().scopes(["service1"]).build())
+            .await
+            .unwrap();
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| (report.monitor_id, report.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("monitor1".into(), "Monitor 1"),
+                ("monitor2".into(), "Monitor 2"),
+                ("monitor4".into(), "Monitor 4"),
+            ],
+        );
+        assert_eq!(reports[0].result, Ok(MonitorMuteStatus::Updated));
+        assert_eq!(reports[1].result, Ok(MonitorMuteStatus::Unchanged));
+        assert!(matches!(reports[2].result, Err(Error::NotFound(_))));
+    }
+
+    #[async_std::test]
+    async fn unmute_monitors() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/monitors",
+            response = json!({
+                "monitors": [entity_json_example()],
+            }),
+        };
+        assert_eq!(
+            test_client!(server)
+                .unmute_monitors(MonitorFilter::builder().ids(["monitor0"]).build())
+                .await,
+            Ok(vec![MonitorMuteReport {
+                monitor_id: "monitor0".into(),
+                name: "Example connectivity monitor".to_owned(),
+                result: Ok(MonitorMuteStatus::Unchanged),
+            }]),
+        );
+    }
 }
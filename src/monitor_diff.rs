@@ -0,0 +1,482 @@
+//! Semantic diff of the monitor definitions
+//!
+//! The monitor values are compared field by field on their JSON representation,
+//! where the default values (for example, the empty memo) are equivalent to the missing fields,
+//! and the lists of strings and numbers (for example, the scopes) are compared as sets.
+//! ```rust
+//! use mackerel_client::monitor::{MonitorOperator, MonitorValue};
+//! use mackerel_client::monitor_diff::MonitorValueDiff;
+//!
+//! let monitor_value = MonitorValue::host()
+//!     .name("Example host monitor")
+//!     .duration(5)
+//!     .metric("loadavg5")
+//!     .operator(MonitorOperator::GreaterThan)
+//!     .critical(20.0)
+//!     .scopes(["service0"])
+//!     .build();
+//! let mut new_monitor_value = monitor_value.clone();
+//! if let MonitorValue::Host { critical, scopes, .. } = &mut new_monitor_value {
+//!     *critical = Some(25.0);
+//!     scopes.push("service0:web".into());
+//! }
+//! let diff = MonitorValueDiff::new(&monitor_value, &new_monitor_value);
+//! assert_eq!(diff.to_string(), "critical: 20.0 -> 25.0\nscopes: +service0:web\n");
+//! ```
+use serde_derive::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::monitor::{Monitor, MonitorId, MonitorValue};
+
+/// A field-level change between two monitor values.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct FieldChange {
+    /// The field name in the JSON representation, for example `alertStatusOnGone`.
+    pub field: String,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// A change of a field.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Change {
+    /// The field is added.
+    Added { to: Value },
+    /// The field is removed.
+    Removed { from: Value },
+    /// The field is modified.
+    Modified { from: Value, to: Value },
+    /// The items of the list field are added or removed.
+    Items {
+        added: Vec<Value>,
+        removed: Vec<Value>,
+    },
+}
+
+impl std::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.field)?;
+        match self.change {
+            Change::Added { ref to } => write!(f, "(none) -> {}", DisplayValue(to)),
+            Change::Removed { ref from } => write!(f, "{} -> (none)", DisplayValue(from)),
+            Change::Modified { ref from, ref to } => {
+                write!(f, "{} -> {}", DisplayValue(from), DisplayValue(to))
+            }
+            Change::Items {
+                ref added,
+                ref removed,
+            } => {
+                let items = added
+                    .iter()
+                    .map(|value| format!("+{}", DisplayValue(value)))
+                    .chain(
+                        removed
+                            .iter()
+                            .map(|value| format!("-{}", DisplayValue(value))),
+                    )
+                    .collect::<Vec<_>>();
+                write!(f, "{}", items.join(", "))
+            }
+        }
+    }
+}
+
+struct DisplayValue<'a>(&'a Value);
+
+impl std::fmt::Display for DisplayValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Value::String(s) => write!(f, "{}", s),
+            value => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A semantic diff between two monitor values.
+#[derive(PartialEq, Clone, Debug, Default, Serialize)]
+pub struct MonitorValueDiff {
+    /// The changes of the fields, sorted by the field names.
+    pub changes: Vec<FieldChange>,
+}
+
+impl MonitorValueDiff {
+    /// Computes the diff from `from` to `to`.
+    pub fn new(from: &MonitorValue, to: &MonitorValue) -> Self {
+        let (from, to) = (normalize(from), normalize(to));
+        let empty_array = Value::Array(Vec::new());
+        let fields = from.keys().chain(to.keys()).collect::<BTreeSet<_>>();
+        let changes = fields
+            .into_iter()
+            .filter_map(|field| {
+                let change = match (from.get(field), to.get(field)) {
+                    (None, Some(to)) if is_scalar_array(to) => diff_values(&empty_array, to)?,
+                    (None, Some(to)) => Change::Added { to: to.clone() },
+                    (Some(from), None) if is_scalar_array(from) => diff_values(from, &empty_array)?,
+                    (Some(from), None) => Change::Removed { from: from.clone() },
+                    (Some(from), Some(to)) => diff_values(from, to)?,
+                    (None, None) => return None,
+                };
+                Some(FieldChange {
+                    field: field.clone(),
+                    change,
+                })
+            })
+            .collect();
+        Self { changes }
+    }
+
+    /// Returns true if the monitor values are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the JSON representation of the diff.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+impl std::fmt::Display for MonitorValueDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+fn normalize(monitor_value: &MonitorValue) -> Map<String, Value> {
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(monitor_value) else {
+        return Map::new();
+    };
+    fields.retain(|_, value| !is_default_value(value));
+    fields
+}
+
+fn is_default_value(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        _ => false,
+    }
+}
+
+fn diff_values(from: &Value, to: &Value) -> Option<Change> {
+    if from == to {
+        return None;
+    }
+    if let (Value::Array(from_values), Value::Array(to_values)) = (from, to) {
+        if is_scalar_array(from) && is_scalar_array(to) {
+            let from_values = from_values.iter().map(ScalarValue).collect::<BTreeSet<_>>();
+            let to_values = to_values.iter().map(ScalarValue).collect::<BTreeSet<_>>();
+            if from_values == to_values {
+                return None;
+            }
+            return Some(Change::Items {
+                added: to_values
+                    .difference(&from_values)
+                    .map(|value| value.0.clone())
+                    .collect(),
+                removed: from_values
+                    .difference(&to_values)
+                    .map(|value| value.0.clone())
+                    .collect(),
+            });
+        }
+    }
+    Some(Change::Modified {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+fn is_scalar_array(value: &Value) -> bool {
+    value.as_array().is_some_and(|values| {
+        values
+            .iter()
+            .all(|value| matches!(value, Value::String(_) | Value::Number(_)))
+    })
+}
+
+/// A scalar value ordered by the JSON representation.
+#[derive(PartialEq, Eq)]
+struct ScalarValue<'a>(&'a Value);
+
+impl Ord for ScalarValue<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.to_string().cmp(&other.0.to_string())
+    }
+}
+
+impl PartialOrd for ScalarValue<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A monitor modified between two sets of monitors.
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct ModifiedMonitor {
+    pub id: MonitorId,
+    pub name: String,
+    pub diff: MonitorValueDiff,
+}
+
+/// A semantic diff between two sets of monitors, matched by the ids.
+/// The monitors are listed in the order of the given sets.
+#[derive(PartialEq, Clone, Debug, Default, Serialize)]
+pub struct MonitorsDiff {
+    pub added: Vec<Monitor>,
+    pub removed: Vec<Monitor>,
+    pub modified: Vec<ModifiedMonitor>,
+}
+
+impl MonitorsDiff {
+    /// Computes the diff from the monitors `from` to `to`.
+    pub fn new(from: &[Monitor], to: &[Monitor]) -> Self {
+        let from_monitors = from
+            .iter()
+            .map(|monitor| (monitor.id, monitor))
+            .collect::<HashMap<_, _>>();
+        let to_monitors = to
+            .iter()
+            .map(|monitor| (monitor.id, monitor))
+            .collect::<HashMap<_, _>>();
+        let mut diff = Self::default();
+        for monitor in from {
+            match to_monitors.get(&monitor.id) {
+                Some(&to_monitor) => {
+                    let value_diff = MonitorValueDiff::new(&monitor.value, &to_monitor.value);
+                    if !value_diff.is_empty() {
+                        diff.modified.push(ModifiedMonitor {
+                            id: monitor.id,
+                            name: to_monitor.name(),
+                            diff: value_diff,
+                        });
+                    }
+                }
+                None => diff.removed.push(monitor.clone()),
+            }
+        }
+        diff.added = to
+            .iter()
+            .filter(|monitor| !from_monitors.contains_key(&monitor.id))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Returns true if the sets of monitors are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Returns the JSON representation of the diff.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+impl std::fmt::Display for MonitorsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for monitor in &self.added {
+            writeln!(f, "+ {:?} ({})", monitor.name(), monitor.id)?;
+        }
+        for monitor in &self.removed {
+            writeln!(f, "- {:?} ({})", monitor.name(), monitor.id)?;
+        }
+        for monitor in &self.modified {
+            writeln!(f, "~ {:?} ({})", monitor.name, monitor.id)?;
+            for change in &monitor.diff.changes {
+                writeln!(f, "    {}", change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use crate::alert::AlertStatus;
+    use crate::monitor::MonitorOperator;
+    use crate::monitor_diff::*;
+
+    fn host_monitor_value() -> MonitorValue {
+        MonitorValue::host()
+            .name("Example host monitor")
+            .duration(5)
+            .metric("loadavg5")
+            .operator(MonitorOperator::GreaterThan)
+            .warning(10.0)
+            .critical(20.0)
+            .scopes(["service0", "service0:db"])
+            .build()
+    }
+
+    #[test]
+    fn diff_monitor_values() {
+        let mut monitor_value = host_monitor_value();
+        if let MonitorValue::Host {
+            ref mut warning,
+            ref mut critical,
+            ref mut scopes,
+            ref mut notification_interval,
+            ..
+        } = monitor_value
+        {
+            *warning = None;
+            *critical = Some(25.0);
+            *scopes = vec!["service0:web".into(), "service0".into()];
+            *notification_interval = Some(60);
+        }
+        monitor_value.set_memo("Monitor memo");
+        let diff = MonitorValueDiff::new(&host_monitor_value(), &monitor_value);
+        assert_eq!(
+            diff.to_string(),
+            "critical: 20.0 -> 25.0\n\
+             memo: (none) -> Monitor memo\n\
+             notificationInterval: (none) -> 60\n\
+             scopes: +service0:web, -service0:db\n\
+             warning: 10.0 -> (none)\n",
+        );
+        assert_eq!(
+            diff.to_json(),
+            json!({
+                "changes": [
+                    { "field": "critical", "kind": "modified", "from": 20.0, "to": 25.0 },
+                    { "field": "memo", "kind": "added", "to": "Monitor memo" },
+                    { "field": "notificationInterval", "kind": "added", "to": 60 },
+                    {
+                        "field": "scopes",
+                        "kind": "items",
+                        "added": ["service0:web"],
+                        "removed": ["service0:db"],
+                    },
+                    { "field": "warning", "kind": "removed", "from": 10.0 },
+                ],
+            }),
+        );
+    }
+
+    #[rstest]
+    #[case(
+        MonitorValue::connectivity().name("Example connectivity monitor").build(),
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .alert_status_on_gone(AlertStatus::Critical)
+            .build(),
+        ""
+    )]
+    #[case(
+        MonitorValue::connectivity().name("Example connectivity monitor").build(),
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .alert_status_on_gone(AlertStatus::Warning)
+            .build(),
+        "alertStatusOnGone: (none) -> WARNING\n"
+    )]
+    #[case(
+        MonitorValue::connectivity().name("Example connectivity monitor").build(),
+        MonitorValue::Unknown(json!({
+            "type": "connectivity",
+            "name": "Example connectivity monitor",
+            "memo": "",
+            "isMute": false,
+            "scopes": [],
+        }).as_object().unwrap().clone()),
+        ""
+    )]
+    #[case(
+        host_monitor_value(),
+        MonitorValue::Unknown(json!({
+            "type": "host",
+            "name": "Example host monitor",
+            "duration": 5,
+            "metric": "loadavg5",
+            "operator": ">",
+            "warning": 10.0,
+            "critical": 20.0,
+            "scopes": ["service0:db", "service0"],
+        }).as_object().unwrap().clone()),
+        ""
+    )]
+    #[case(
+        host_monitor_value(),
+        MonitorValue::connectivity().name("Example host monitor").build(),
+        "critical: 20.0 -> (none)\n\
+         duration: 5 -> (none)\n\
+         metric: loadavg5 -> (none)\n\
+         operator: > -> (none)\n\
+         scopes: -service0, -service0:db\n\
+         type: host -> connectivity\n\
+         warning: 10.0 -> (none)\n"
+    )]
+    #[case(
+        MonitorValue::connectivity().name("Example connectivity monitor").build(),
+        MonitorValue::connectivity()
+            .name("Example connectivity monitor")
+            .extra(json!({ "newSetting": { "key": "value" } }).as_object().unwrap().clone())
+            .build(),
+        "newSetting: (none) -> {\"key\":\"value\"}\n"
+    )]
+    fn diff_monitor_values_semantically(
+        #[case] from: MonitorValue,
+        #[case] to: MonitorValue,
+        #[case] diff_str: &str,
+    ) {
+        let diff = MonitorValueDiff::new(&from, &to);
+        assert_eq!(diff.to_string(), diff_str);
+        assert_eq!(diff.is_empty(), diff_str.is_empty());
+    }
+
+    #[test]
+    fn diff_monitors() {
+        let mut monitor_value = host_monitor_value();
+        monitor_value.set_name("Updated host monitor");
+        let from = vec![
+            Monitor::new("monitor1", host_monitor_value()),
+            Monitor::new("monitor2", host_monitor_value()),
+            Monitor::new("monitor3", host_monitor_value()),
+        ];
+        let to = vec![
+            Monitor::new("monitor4", host_monitor_value()),
+            Monitor::new("monitor3", host_monitor_value()),
+            Monitor::new("monitor1", monitor_value),
+        ];
+        let diff = MonitorsDiff::new(&from, &to);
+        assert_eq!(diff.added, vec![to[0].clone()]);
+        assert_eq!(diff.removed, vec![from[1].clone()]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(
+            diff.to_string(),
+            "+ \"Example host monitor\" (monitor4)\n\
+             - \"Example host monitor\" (monitor2)\n\
+             ~ \"Updated host monitor\" (monitor1)\n    \
+             name: Example host monitor -> Updated host monitor\n",
+        );
+        assert_eq!(
+            diff.to_json()["modified"],
+            json!([{
+                "id": "monitor1",
+                "name": "Updated host monitor",
+                "diff": {
+                    "changes": [{
+                        "field": "name",
+                        "kind": "modified",
+                        "from": "Example host monitor",
+                        "to": "Updated host monitor",
+                    }],
+                },
+            }]),
+        );
+        assert!(!diff.is_empty());
+        assert!(MonitorsDiff::new(&from, &from).is_empty());
+    }
+}
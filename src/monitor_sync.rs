@@ -0,0 +1,490 @@
+//! Monitors as code: export and apply the monitor definitions
+//!
+//! The monitors are exported to a JSON document of the monitor definitions without ids,
+//! sorted by the type and the name of the monitors, with the keys sorted.
+//! The local definitions are matched to the remote monitors by the type and the name.
+//! The document is read and written in JSON only; YAML is out of scope of this module.
+//! ```rust,no_run
+//! use mackerel_client::monitor_sync::MonitorsDocument;
+//! use mackerel_client::Client;
+//!
+//! # #[async_std::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::new("<Mackerel-API-KEY>");
+//!
+//! // Exports the monitors to the file.
+//! client.export_monitors().await?.save("monitors.json")?;
+//!
+//! // Plans the changes of the monitors, and applies them.
+//! let document = MonitorsDocument::load("monitors.json")?;
+//! let plan = client.plan_monitors(&document).await?;
+//! print!("{}", plan);
+//! client.apply_monitors_plan(&plan).await?;
+//! # Ok(())
+//! # }
+//! ```
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::client::*;
+use crate::error::Result;
+use crate::monitor::{Monitor, MonitorId, MonitorValue};
+use crate::monitor_diff::MonitorValueDiff;
+
+/// A document of the monitor definitions.
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MonitorsDocument {
+    pub monitors: Vec<MonitorValue>,
+}
+
+impl MonitorsDocument {
+    /// Creates a new document from the monitors, sorted by the type and the name.
+    pub fn from_monitors(monitors: impl IntoIterator<Item = Monitor>) -> Self {
+        let mut monitors = monitors
+            .into_iter()
+            .map(|monitor| monitor.value)
+            .collect::<Vec<_>>();
+        monitors.sort_by_cached_key(monitor_key);
+        Self { monitors }
+    }
+
+    /// Parses the document from the JSON string.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the JSON string of the document, with the keys sorted.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let value = canonicalize(serde_json::to_value(self)?);
+        Ok(serde_json::to_string_pretty(&value)? + "\n")
+    }
+
+    /// Loads the document from the file.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_json(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Saves the document to the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+}
+
+// Sorts the keys of the objects recursively, regardless of the preserve_order feature of serde_json.
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| (key, canonicalize(value)))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect(),
+        serde_json::Value::Array(values) => values.into_iter().map(canonicalize).collect(),
+        value => value,
+    }
+}
+
+fn monitor_key(monitor_value: &MonitorValue) -> (String, String) {
+    let monitor_type = match *monitor_value {
+        MonitorValue::Unknown(ref fields) => fields
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_owned(),
+        _ => monitor_value
+            .monitor_type()
+            .map(|monitor_type| monitor_type.to_string())
+            .unwrap_or_default(),
+    };
+    (monitor_type, monitor_value.name())
+}
+
+/// A change of a monitor planned by [`Client::plan_monitors`].
+#[derive(PartialEq, Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum MonitorChange {
+    /// Creates a monitor defined locally.
+    Create(MonitorValue),
+    /// Updates the remote monitor with the local definition.
+    Update {
+        id: MonitorId,
+        from: MonitorValue,
+        to: MonitorValue,
+    },
+    /// Deletes the remote monitor not defined locally.
+    Delete(Monitor),
+}
+
+impl std::fmt::Display for MonitorChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create(monitor_value) => {
+                let (monitor_type, name) = monitor_key(monitor_value);
+                write!(f, "create {} monitor {:?}", monitor_type, name)
+            }
+            Self::Update { id, to, .. } => {
+                let (monitor_type, name) = monitor_key(to);
+                write!(f, "update {} monitor {:?} ({})", monitor_type, name, id)
+            }
+            Self::Delete(monitor) => {
+                let (monitor_type, name) = monitor_key(&monitor.value);
+                write!(
+                    f,
+                    "delete {} monitor {:?} ({})",
+                    monitor_type, name, monitor.id
+                )
+            }
+        }
+    }
+}
+
+/// The changes of the monitors planned by [`Client::plan_monitors`].
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct MonitorsPlan {
+    pub changes: Vec<MonitorChange>,
+}
+
+impl MonitorsPlan {
+    /// Returns true if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl std::fmt::Display for MonitorsPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the changes to make the remote monitors match the local definitions.
+/// The definitions are matched to the monitors by the type and the name,
+/// and the duplicated definitions are matched in order.
+/// The monitor is not updated if the definition is semantically equivalent (see [`MonitorValueDiff`]).
+pub fn plan(document: &MonitorsDocument, monitors: Vec<Monitor>) -> MonitorsPlan {
+    let mut monitors = monitors.into_iter().map(Some).collect::<Vec<_>>();
+    let mut changes = Vec::new();
+    for monitor_value in &document.monitors {
+        let key = monitor_key(monitor_value);
+        let monitor_opt = monitors
+            .iter_mut()
+            .find(|monitor_opt| {
+                monitor_opt
+                    .as_ref()
+                    .is_some_and(|monitor| monitor_key(&monitor.value) == key)
+            })
+            .and_then(Option::take);
+        match monitor_opt {
+            Some(monitor) if MonitorValueDiff::new(&monitor.value, monitor_value).is_empty() => {}
+            Some(monitor) => changes.push(MonitorChange::Update {
+                id: monitor.id,
+                from: monitor.value,
+                to: monitor_value.clone(),
+            }),
+            None => changes.push(MonitorChange::Create(monitor_value.clone())),
+        }
+    }
+    let mut deleted_monitors = monitors.into_iter().flatten().collect::<Vec<_>>();
+    deleted_monitors.sort_by_cached_key(|monitor| monitor_key(&monitor.value));
+    changes.extend(deleted_monitors.into_iter().map(MonitorChange::Delete));
+    MonitorsPlan { changes }
+}
+
+client_methods! {
+    impl Client {
+        /// Exports all the monitors to the document.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn export_monitors(&self) -> Result<MonitorsDocument> {
+            Ok(MonitorsDocument::from_monitors(self.list_monitors().await?))
+        }
+
+        /// Plans the changes to make the monitors match the document, without applying them.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn plan_monitors(
+            &self,
+            document: impl Borrow<MonitorsDocument>,
+        ) -> Result<MonitorsPlan> {
+            Ok(plan(document.borrow(), self.list_monitors().await?))
+        }
+
+        /// Applies the planned changes of the monitors in order.
+        /// Applying stops at the first failed change.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn apply_monitors_plan(&self, plan: impl Borrow<MonitorsPlan>) -> Result<()> {
+            for change in &plan.borrow().changes {
+                match change {
+                    MonitorChange::Create(monitor_value) => {
+                        self.create_monitor(monitor_value).await?;
+                    }
+                    MonitorChange::Update { id, to, .. } => {
+                        self.update_monitor(*id, to).await?;
+                    }
+                    MonitorChange::Delete(monitor) => {
+                        self.delete_monitor(monitor.id).await?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Applies the document to the monitors, and returns the applied plan.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/monitors>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn apply_monitors(
+            &self,
+            document: impl Borrow<MonitorsDocument>,
+        ) -> Result<MonitorsPlan> {
+            let plan = self.plan_monitors(document).await?;
+            self.apply_monitors_plan(&plan).await?;
+            Ok(plan)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::monitor::{MonitorOperator, MonitorType};
+    use crate::monitor_sync::*;
+
+    fn host_monitor_value(name: &str, critical: f64) -> MonitorValue {
+        MonitorValue::host()
+            .name(name)
+            .duration(5)
+            .metric("loadavg5")
+            .operator(MonitorOperator::GreaterThan)
+            .critical(critical)
+            .build()
+    }
+
+    fn connectivity_monitor_value(name: &str) -> MonitorValue {
+        MonitorValue::connectivity().name(name).build()
+    }
+
+    #[test]
+    fn document_json() {
+        let document = MonitorsDocument::from_monitors([
+            Monitor::new("monitor1", host_monitor_value("Monitor B", 20.0)),
+            Monitor::new("monitor2", connectivity_monitor_value("Monitor C")),
+            Monitor::new("monitor3", host_monitor_value("Monitor A", 10.0)),
+        ]);
+        assert_eq!(
+            document.monitors,
+            vec![
+                connectivity_monitor_value("Monitor C"),
+                host_monitor_value("Monitor A", 10.0),
+                host_monitor_value("Monitor B", 20.0),
+            ],
+        );
+        let json = document.to_json().unwrap();
+        assert_eq!(
+            json,
+            serde_json::to_string_pretty(&json!({
+                "monitors": [
+                    { "name": "Monitor C", "type": "connectivity" },
+                    {
+                        "critical": 10.0,
+                        "duration": 5,
+                        "metric": "loadavg5",
+                        "name": "Monitor A",
+                        "operator": ">",
+                        "type": "host",
+                    },
+                    {
+                        "critical": 20.0,
+                        "duration": 5,
+                        "metric": "loadavg5",
+                        "name": "Monitor B",
+                        "operator": ">",
+                        "type": "host",
+                    },
+                ],
+            }))
+            .unwrap()
+                + "\n",
+        );
+        assert!(json.starts_with(
+            "{\n  \"monitors\": [\n    {\n      \"name\": \"Monitor C\",\n      \"type\": \"connectivity\"\n    },"
+        ));
+        assert_eq!(MonitorsDocument::from_json(&json).ok(), Some(document));
+    }
+
+    #[test]
+    fn canonicalize_keys() {
+        let value = serde_json::from_str(r#"{"b":[{"d":1,"c":2}],"a":{"f":3,"e":4}}"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&canonicalize(value)).unwrap(),
+            r#"{"a":{"e":4,"f":3},"b":[{"c":2,"d":1}]}"#,
+        );
+    }
+
+    #[test]
+    fn document_file() {
+        let path = std::env::temp_dir().join(format!(
+            "mackerel-client-rs-test-monitors-{}.json",
+            std::process::id()
+        ));
+        let document = MonitorsDocument {
+            monitors: vec![connectivity_monitor_value("Monitor A")],
+        };
+        document.save(&path).unwrap();
+        assert_eq!(MonitorsDocument::load(&path).ok(), Some(document));
+        std::fs::remove_file(&path).unwrap();
+        assert!(MonitorsDocument::load(&path).is_err());
+    }
+
+    #[test]
+    fn plan_monitors() {
+        let document = MonitorsDocument {
+            monitors: vec![
+                host_monitor_value("Monitor A", 10.0),
+                host_monitor_value("Monitor B", 25.0),
+                connectivity_monitor_value("Monitor C"),
+                connectivity_monitor_value("Monitor C"),
+            ],
+        };
+        let monitors = vec![
+            Monitor::new("monitor1", connectivity_monitor_value("Monitor A")),
+            Monitor::new("monitor2", host_monitor_value("Monitor B", 20.0)),
+            Monitor::new("monitor3", connectivity_monitor_value("Monitor C")),
+            Monitor::new("monitor4", host_monitor_value("Monitor A", 10.0)),
+        ];
+        let plan = plan(&document, monitors);
+        assert_eq!(
+            plan.changes,
+            vec![
+                MonitorChange::Update {
+                    id: "monitor2".into(),
+                    from: host_monitor_value("Monitor B", 20.0),
+                    to: host_monitor_value("Monitor B", 25.0),
+                },
+                MonitorChange::Create(connectivity_monitor_value("Monitor C")),
+                MonitorChange::Delete(Monitor::new(
+                    "monitor1",
+                    connectivity_monitor_value("Monitor A"),
+                )),
+            ],
+        );
+        assert_eq!(
+            plan.to_string(),
+            "update host monitor \"Monitor B\" (monitor2)\n\
+             create connectivity monitor \"Monitor C\"\n\
+             delete connectivity monitor \"Monitor A\" (monitor1)\n",
+        );
+        assert!(!plan.is_empty());
+    }
+
+    #[test]
+    fn plan_equivalent_monitors() {
+        let monitor_value = |scopes: [&str; 2]| {
+            MonitorValue::connectivity()
+                .name("Monitor A")
+                .scopes(scopes)
+                .build()
+        };
+        let document = MonitorsDocument {
+            monitors: vec![monitor_value(["service1", "service2"])],
+        };
+        let monitors = vec![Monitor::new(
+            "monitor1",
+            monitor_value(["service2", "service1"]),
+        )];
+        assert!(plan(&document, monitors).is_empty());
+    }
+
+    #[test]
+    fn plan_unknown_monitors() {
+        let unknown_monitor_value = |name: &str| {
+            MonitorValue::Unknown(
+                json!({ "type": "newMonitorType", "name": name })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            )
+        };
+        let document = MonitorsDocument {
+            monitors: vec![unknown_monitor_value("Monitor A")],
+        };
+        let monitors = vec![Monitor::new("monitor1", unknown_monitor_value("Monitor A"))];
+        assert!(plan(&document, monitors).is_empty());
+        assert_eq!(
+            MonitorChange::Create(unknown_monitor_value("Monitor B")).to_string(),
+            "create newMonitorType monitor \"Monitor B\"",
+        );
+        assert_eq!(document.monitors[0].monitor_type(), None::<MonitorType>,);
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod client_tests {
+    use httptest::{matchers::*, responders::*, Expectation};
+
+    use crate::monitor_sync::*;
+    use crate::test_util::fixtures;
+    use crate::tests::*;
+
+    #[async_std::test]
+    async fn export_monitors() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/monitors",
+            response = json!({ "monitors": [fixtures::monitor()] }),
+        };
+        assert_eq!(
+            test_client!(server).export_monitors().await,
+            Ok(MonitorsDocument {
+                monitors: vec![fixtures::monitor().value],
+            }),
+        );
+    }
+
+    #[async_std::test]
+    async fn apply_monitors() {
+        let mut monitor_value = fixtures::monitor().value;
+        monitor_value.set_memo("Updated memo");
+        let document = MonitorsDocument {
+            monitors: vec![monitor_value.clone()],
+        };
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/monitors",
+            response = json!({ "monitors": [fixtures::monitor()] }),
+        };
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("PUT", "/api/v0/monitors/monitor1"),
+                request::body(json_decoded(eq(
+                    serde_json::to_value(&monitor_value).unwrap()
+                ))),
+            ])
+            .respond_with(json_encoded(Monitor::new(
+                "monitor1",
+                monitor_value.clone(),
+            ))),
+        );
+        let client = test_client!(server);
+        let plan = client.plan_monitors(&document).await.unwrap();
+        assert_eq!(
+            plan.changes,
+            vec![MonitorChange::Update {
+                id: "monitor1".into(),
+                from: fixtures::monitor().value,
+                to: monitor_value,
+            }],
+        );
+        assert_eq!(client.apply_monitors(&document).await, Ok(plan));
+    }
+}
@@ -45,6 +45,10 @@ pub struct NotificationGroupValue {
     #[builder(default)]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub services: Vec<NotificationGroupService>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A notification level
@@ -152,75 +156,87 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the notification groups.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/notification-groups#get>.
-    pub async fn list_notification_groups(&self) -> Result<Vec<NotificationGroup>> {
-        self.request(
-            Method::GET,
-            "/api/v0/notification-groups",
-            query_params![],
-            request_body![],
-            response_body! { notificationGroups: Vec<NotificationGroup> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the notification groups.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/notification-groups#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_notification_groups(&self) -> Result<Vec<NotificationGroup>> {
+            self.request(
+                Method::GET,
+                "/api/v0/notification-groups",
+                query_params![],
+                request_body![],
+                response_body! { notificationGroups: Vec<NotificationGroup> },
+            )
+            .await
+        }
 
-    /// Creates a new notification group.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/notification-groups#create>.
-    pub async fn create_notification_group(
-        &self,
-        notification_group_value: impl Borrow<NotificationGroupValue>,
-    ) -> Result<NotificationGroup> {
-        self.request(
-            Method::POST,
-            "/api/v0/notification-groups",
-            query_params![],
-            request_body!(notification_group_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new notification group.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/notification-groups#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_notification_group(
+            &self,
+            notification_group_value: impl Borrow<NotificationGroupValue>,
+        ) -> Result<NotificationGroup> {
+            self.request(
+                Method::POST,
+                "/api/v0/notification-groups",
+                query_params![],
+                request_body!(notification_group_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Updates a notification group.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/notification-groups#update>.
-    pub async fn update_notification_group(
-        &self,
-        notification_group_id: impl Into<NotificationGroupId>,
-        notification_group_value: impl Borrow<NotificationGroupValue>,
-    ) -> Result<NotificationGroup> {
-        self.request(
-            Method::PUT,
-            format_url!("/api/v0/notification-groups/{}", notification_group_id),
-            query_params![],
-            request_body!(notification_group_value.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Updates a notification group.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/notification-groups#update>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(notification_group_id))
+        )]
+        pub async fn update_notification_group(
+            &self,
+            notification_group_id: impl Into<NotificationGroupId>,
+            notification_group_value: impl Borrow<NotificationGroupValue>,
+        ) -> Result<NotificationGroup> {
+            self.request(
+                Method::PUT,
+                format_url!("/api/v0/notification-groups/{}", notification_group_id),
+                query_params![],
+                request_body!(notification_group_value.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes a notification group.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/notification-groups#delete>.
-    pub async fn delete_notification_group(
-        &self,
-        notification_group_id: impl Into<NotificationGroupId>,
-    ) -> Result<NotificationGroup> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/notification-groups/{}", notification_group_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Deletes a notification group.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/notification-groups#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(notification_group_id))
+        )]
+        pub async fn delete_notification_group(
+            &self,
+            notification_group_id: impl Into<NotificationGroupId>,
+        ) -> Result<NotificationGroup> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/notification-groups/{}", notification_group_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -15,6 +15,10 @@ pub struct Organization {
     #[builder(default, setter(strip_option))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An organization name
@@ -64,23 +68,26 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Retrieve the information on the organization.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/organizations#get>.
-    pub async fn get_organization(&self) -> Result<Organization> {
-        self.request(
-            Method::GET,
-            "/api/v0/org",
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+client_methods! {
+    impl Client {
+        /// Retrieve the information on the organization.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/organizations#get>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn get_organization(&self) -> Result<Organization> {
+            self.request(
+                Method::GET,
+                "/api/v0/org",
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use crate::organization::*;
     use crate::tests::*;
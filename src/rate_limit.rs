@@ -0,0 +1,185 @@
+//! Client-side rate limiting of the API client
+use http::Method;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use typed_builder::TypedBuilder;
+
+/// A rate limit of the API requests, based on the token bucket algorithm.
+///
+/// The requests exceeding the limit wait until a token is available,
+/// instead of being rejected by the API with `429 Too Many Requests`.
+#[cfg_attr(feature = "reqwest", doc = "```rust")]
+#[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+/// use mackerel_client::rate_limit::RateLimit;
+/// use mackerel_client::Client;
+///
+/// let client = Client::builder()
+///     .api_key("<Mackerel-API-KEY>")
+///     .rate_limit(RateLimit::builder().requests_per_minute(600).burst(20).build())
+///     .write_rate_limit(RateLimit::per_minute(120))
+///     .build();
+/// ```
+#[derive(PartialEq, Eq, Copy, Clone, Debug, TypedBuilder)]
+pub struct RateLimit {
+    /// The number of requests allowed per minute.
+    pub requests_per_minute: u32,
+    /// The number of requests allowed to be sent at once.
+    #[builder(default = 1)]
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Creates a new [`RateLimit`] without bursting.
+    pub fn per_minute(requests_per_minute: u32) -> Self {
+        Self::builder()
+            .requests_per_minute(requests_per_minute)
+            .build()
+    }
+}
+
+/// A rate limiter shared by all the requests of the client.
+/// The write requests use the separate bucket if the write rate limit is configured.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    read_bucket: Option<Arc<TokenBucket>>,
+    write_bucket: Option<Arc<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(
+        rate_limit_opt: Option<RateLimit>,
+        write_rate_limit_opt: Option<RateLimit>,
+    ) -> Self {
+        let read_bucket = rate_limit_opt.map(TokenBucket::new).map(Arc::new);
+        let write_bucket = write_rate_limit_opt
+            .map(TokenBucket::new)
+            .map(Arc::new)
+            .or(read_bucket.clone());
+        Self {
+            read_bucket,
+            write_bucket,
+        }
+    }
+
+    /// Waits until the request of the method is allowed to be sent.
+    pub(crate) async fn acquire(&self, method: &Method) {
+        let bucket_opt = if method.is_safe() {
+            &self.read_bucket
+        } else {
+            &self.write_bucket
+        };
+        if let Some(bucket) = bucket_opt {
+            let delay = bucket.reserve(Instant::now());
+            if !delay.is_zero() {
+                futures_timer::Delay::new(delay).await;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens_per_second: f64,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_limit: RateLimit) -> Self {
+        let capacity = rate_limit.burst.max(1) as f64;
+        Self {
+            capacity,
+            tokens_per_second: rate_limit.requests_per_minute.max(1) as f64 / 60.0,
+            state: Mutex::new(TokenBucketState {
+                tokens: capacity,
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token and returns the duration to wait for it.
+    /// The tokens may go negative so that the waiting requests are served in order.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        let elapsed = now.saturating_duration_since(state.updated_at);
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * self.tokens_per_second).min(self.capacity);
+        state.updated_at = state.updated_at.max(now);
+        state.tokens -= 1.0;
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.tokens_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let bucket = TokenBucket::new(
+            RateLimit::builder()
+                .requests_per_minute(600)
+                .burst(2)
+                .build(),
+        );
+        let now = Instant::now();
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+        let now = now + Duration::from_millis(200);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        let now = now + Duration::from_secs(10);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let rate_limiter = RateLimiter::new(Some(RateLimit::per_minute(60)), None);
+        assert!(Arc::ptr_eq(
+            rate_limiter.read_bucket.as_ref().unwrap(),
+            rate_limiter.write_bucket.as_ref().unwrap(),
+        ));
+        let rate_limiter = RateLimiter::new(
+            Some(RateLimit::per_minute(60)),
+            Some(RateLimit::per_minute(30)),
+        );
+        assert!(!Arc::ptr_eq(
+            rate_limiter.read_bucket.as_ref().unwrap(),
+            rate_limiter.write_bucket.as_ref().unwrap(),
+        ));
+        let rate_limiter = RateLimiter::new(None, Some(RateLimit::per_minute(30)));
+        assert!(rate_limiter.read_bucket.is_none());
+        assert!(rate_limiter.write_bucket.is_some());
+    }
+
+    #[async_std::test]
+    async fn test_rate_limiter_acquire() {
+        let rate_limiter = RateLimiter::new(
+            Some(
+                RateLimit::builder()
+                    .requests_per_minute(6000)
+                    .burst(3)
+                    .build(),
+            ),
+            None,
+        );
+        let start = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.acquire(&Method::GET).await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
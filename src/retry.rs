@@ -0,0 +1,188 @@
+//! Retry policy of the API client
+use chrono::{DateTime, Utc};
+use http::{header::RETRY_AFTER, HeaderMap, Method, StatusCode};
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+
+/// A retry policy of the API client.
+///
+/// The default policy retries idempotent requests up to three attempts
+/// on rate limiting, server errors and connection failures.
+#[cfg_attr(feature = "reqwest", doc = "```rust")]
+#[cfg_attr(not(feature = "reqwest"), doc = "```rust,ignore")]
+/// use mackerel_client::retry::RetryPolicy;
+/// use mackerel_client::Client;
+/// use std::time::Duration;
+///
+/// let client = Client::builder()
+///     .api_key("<Mackerel-API-KEY>")
+///     .retry_policy(
+///         RetryPolicy::builder()
+///             .max_attempts(5)
+///             .base_delay(Duration::from_secs(1))
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first request.
+    #[builder(default = 3, setter(!into))]
+    pub max_attempts: u32,
+    /// The delay before the first retry, which is doubled on each retry.
+    #[builder(default = Duration::from_millis(500))]
+    pub base_delay: Duration,
+    /// The upper bound of the exponential backoff delay.
+    #[builder(default = Duration::from_secs(30))]
+    pub max_delay: Duration,
+    /// Randomizes the delay to avoid synchronized retries.
+    #[builder(default = true)]
+    pub jitter: bool,
+    /// The status codes of responses to be retried.
+    #[builder(default = vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ])]
+    pub retryable_status_codes: Vec<StatusCode>,
+    /// The HTTP methods of requests to be retried.
+    #[builder(default = vec![Method::GET, Method::PUT, Method::DELETE, Method::HEAD, Method::OPTIONS])]
+    pub retryable_methods: Vec<Method>,
+    /// Waits for the duration of the `Retry-After` response header if present,
+    /// which is bounded by `max_delay`.
+    #[builder(default = true)]
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy which never retries.
+    pub fn none() -> Self {
+        Self::builder().max_attempts(1).build()
+    }
+
+    pub(crate) fn can_retry(&self, method: &Method, attempt: u32) -> bool {
+        attempt < self.max_attempts && self.retryable_methods.contains(method)
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_status_codes.contains(&status)
+    }
+
+    pub(crate) fn is_retryable_error(&self, err: &crate::error::TransportError) -> bool {
+        err.is_retryable()
+    }
+
+    /// Returns the delay before the next attempt, where `attempt` is the number of attempts so far.
+    pub(crate) fn delay(&self, attempt: u32, headers_opt: Option<&HeaderMap>) -> Duration {
+        if self.respect_retry_after {
+            if let Some(retry_after) = headers_opt.and_then(retry_after) {
+                return retry_after.min(self.max_delay);
+            }
+        }
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        if self.jitter {
+            delay / 2 + delay.mul_f64(fastrand::f64() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Parses the `Retry-After` header in either delay seconds or HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now())
+        .to_std()
+        .ok()
+        .or(Some(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{TransportError, TransportErrorKind};
+    use http::HeaderValue;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, Duration::from_millis(100))]
+    #[case(2, Duration::from_millis(200))]
+    #[case(3, Duration::from_millis(400))]
+    #[case(5, Duration::from_millis(1000))]
+    #[case(100, Duration::from_millis(1000))]
+    fn test_retry_delay(#[case] attempt: u32, #[case] delay: Duration) {
+        let policy = RetryPolicy::builder()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1))
+            .jitter(false)
+            .build();
+        assert_eq!(policy.delay(attempt, None), delay);
+    }
+
+    #[test]
+    fn test_retry_delay_jitter() {
+        let policy = RetryPolicy::builder()
+            .base_delay(Duration::from_millis(100))
+            .build();
+        for _ in 0..100 {
+            let delay = policy.delay(2, None);
+            assert!((Duration::from_millis(100)..=Duration::from_millis(200)).contains(&delay));
+        }
+    }
+
+    #[rstest]
+    #[case("120", Some(Duration::from_secs(120)), Duration::from_secs(30))]
+    #[case(" 3 ", Some(Duration::from_secs(3)), Duration::from_secs(3))]
+    #[case("Wed, 21 Oct 2015 07:28:00 GMT", Some(Duration::ZERO), Duration::ZERO)]
+    #[case("invalid", None, Duration::from_millis(500))]
+    fn test_retry_after(
+        #[case] header_value: &str,
+        #[case] retry_after_opt: Option<Duration>,
+        #[case] delay: Duration,
+    ) {
+        let headers =
+            HeaderMap::from_iter([(RETRY_AFTER, HeaderValue::from_str(header_value).unwrap())]);
+        assert_eq!(retry_after(&headers), retry_after_opt);
+        let policy = RetryPolicy::builder().jitter(false).build();
+        assert_eq!(policy.delay(1, Some(&headers)), delay);
+    }
+
+    #[test]
+    fn test_can_retry() {
+        let policy = RetryPolicy::default();
+        assert!(policy.can_retry(&Method::GET, 1));
+        assert!(policy.can_retry(&Method::DELETE, 2));
+        assert!(!policy.can_retry(&Method::GET, 3));
+        assert!(!policy.can_retry(&Method::POST, 1));
+        assert!(!RetryPolicy::none().can_retry(&Method::GET, 1));
+        assert!(policy.is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!policy.is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[rstest]
+    #[case(TransportErrorKind::Connect, true)]
+    #[case(TransportErrorKind::Timeout, true)]
+    #[case(TransportErrorKind::Request, true)]
+    #[case(TransportErrorKind::Body, true)]
+    #[case(TransportErrorKind::Other, false)]
+    fn test_retryable_error(#[case] kind: TransportErrorKind, #[case] retryable: bool) {
+        let err = TransportError::new(kind, "transport error");
+        assert_eq!(RetryPolicy::default().is_retryable_error(&err), retryable);
+    }
+}
@@ -16,6 +16,10 @@ pub struct Role {
     pub name: RoleName,
     #[builder(default)]
     pub memo: String,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A role name
@@ -178,59 +182,73 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches the roles in the specified service.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#rolelist>.
-    pub async fn list_roles(&self, service_name: impl Into<ServiceName>) -> Result<Vec<Role>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/services/{}/roles", service_name),
-            query_params![],
-            request_body![],
-            response_body! { roles: Vec<Role> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches the roles in the specified service.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#rolelist>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn list_roles(&self, service_name: impl Into<ServiceName>) -> Result<Vec<Role>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/services/{}/roles", service_name),
+                query_params![],
+                request_body![],
+                response_body! { roles: Vec<Role> },
+            )
+            .await
+        }
 
-    /// Creates a new role.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#rolecreate>.
-    pub async fn create_role(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role: impl Borrow<Role>,
-    ) -> Result<Role> {
-        self.request(
-            Method::POST,
-            format_url!("/api/v0/services/{}/roles", service_name),
-            query_params![],
-            request_body!(role.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new role.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#rolecreate>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn create_role(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role: impl Borrow<Role>,
+        ) -> Result<Role> {
+            self.request(
+                Method::POST,
+                format_url!("/api/v0/services/{}/roles", service_name),
+                query_params![],
+                request_body!(role.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes a role.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#roledelete>.
-    pub async fn delete_role(
-        &self,
-        service_name: impl Into<ServiceName>,
-        role_name: impl Into<RoleName>,
-    ) -> Result<Role> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/services/{}/roles/{}", service_name, role_name),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Deletes a role.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#roledelete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name, role_name))
+        )]
+        pub async fn delete_role(
+            &self,
+            service_name: impl Into<ServiceName>,
+            role_name: impl Into<RoleName>,
+        ) -> Result<Role> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/services/{}/roles/{}", service_name, role_name),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
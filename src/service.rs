@@ -21,6 +21,10 @@ pub struct Service {
             .into_iter().map(Into::into).collect::<Vec<_>>()),
     )]
     pub roles: Vec<RoleName>,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A service name
@@ -74,68 +78,83 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the services.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#list>.
-    pub async fn list_services(&self) -> Result<Vec<Service>> {
-        self.request(
-            Method::GET,
-            "/api/v0/services",
-            query_params![],
-            request_body![],
-            response_body! { services: Vec<Service> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the services.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_services(&self) -> Result<Vec<Service>> {
+            self.request(
+                Method::GET,
+                "/api/v0/services",
+                query_params![],
+                request_body![],
+                response_body! { services: Vec<Service> },
+            )
+            .await
+        }
 
-    /// Creates a new service.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#create>.
-    pub async fn create_service(&self, service: impl Borrow<Service>) -> Result<Service> {
-        self.request(
-            Method::POST,
-            "/api/v0/services",
-            query_params![],
-            request_body!(service.borrow()),
-            response_body!(..),
-        )
-        .await
-    }
+        /// Creates a new service.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#create>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn create_service(&self, service: impl Borrow<Service>) -> Result<Service> {
+            self.request(
+                Method::POST,
+                "/api/v0/services",
+                query_params![],
+                request_body!(service.borrow()),
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Deletes a service.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#delete>.
-    pub async fn delete_service(&self, service_name: impl Into<ServiceName>) -> Result<Service> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/services/{}", service_name),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
-    }
+        /// Deletes a service.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#delete>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn delete_service(
+            &self,
+            service_name: impl Into<ServiceName>,
+        ) -> Result<Service> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/services/{}", service_name),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
 
-    /// Fetches service metric names.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/services#metric-names>.
-    pub async fn list_service_metric_names(
-        &self,
-        service_name: impl Into<ServiceName>,
-    ) -> Result<Vec<String>> {
-        self.request(
-            Method::GET,
-            format_url!("/api/v0/services/{}/metric-names", service_name),
-            query_params![],
-            request_body![],
-            response_body! { names: Vec<String> },
-        )
-        .await
+        /// Fetches service metric names.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/services#metric-names>.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(skip_all, fields(service_name))
+        )]
+        pub async fn list_service_metric_names(
+            &self,
+            service_name: impl Into<ServiceName>,
+        ) -> Result<Vec<String>> {
+            self.request(
+                Method::GET,
+                format_url!("/api/v0/services/{}/metric-names", service_name),
+                query_params![],
+                request_body![],
+                response_body! { names: Vec<String> },
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 
@@ -0,0 +1,337 @@
+//! Test utilities for the crates built on the API client
+//!
+//! [`test_server!`](crate::test_server) starts a mock server expecting the request,
+//! and [`test_client!`](crate::test_client) creates a client for the server.
+//! ```rust
+//! use mackerel_client::test_util::{fixtures, GET};
+//! use mackerel_client::{test_client, test_server};
+//!
+//! # #[async_std::main]
+//! # async fn main() {
+//! let server = test_server! {
+//!     method = GET,
+//!     path = "/api/v0/hosts/host1",
+//!     headers = [("x-api-key", "<Mackerel-API-KEY>")],
+//!     response = json!({ "host": fixtures::host() }),
+//! };
+//! let client = test_client!(server, "<Mackerel-API-KEY>");
+//! assert_eq!(client.get_host("host1").await, Ok(fixtures::host()));
+//! # }
+//! ```
+use http::StatusCode;
+use httptest::ServerPool;
+use serde_json::Value;
+
+#[doc(hidden)]
+pub use httptest;
+#[doc(hidden)]
+pub use serde_json;
+
+/// The pool of the mock servers used by [`test_server!`](crate::test_server).
+pub static TEST_SERVER_POOL: ServerPool = ServerPool::new(3);
+
+/// The expected request and the response of [`test_server!`](crate::test_server).
+#[derive(Default)]
+pub struct TestServerConfig {
+    pub method: &'static str,
+    pub path: &'static str,
+    /// The expected query parameters, for example `"key1=value1&key2=value2"`.
+    pub query_params: &'static str,
+    /// The expected headers in addition to `x-api-key`, `user-agent` and `content-type`.
+    pub headers: Vec<(&'static str, &'static str)>,
+    /// The expected JSON request body, or [`Value::Null`] for the empty body.
+    pub request: Value,
+    pub status_code: StatusCode,
+    pub response: Value,
+}
+
+pub const GET: &str = "GET";
+pub const POST: &str = "POST";
+pub const PUT: &str = "PUT";
+pub const DELETE: &str = "DELETE";
+
+/// Starts a mock server expecting the request configured by the fields of [`TestServerConfig`].
+/// The `json!` macro is available in the field values.
+#[macro_export]
+macro_rules! test_server {
+    ($( $field:ident = $value:expr ),* $(,)? ) => {{
+        use $crate::test_util::httptest::{all_of, matchers::*, responders, Expectation};
+        use $crate::test_util::serde_json::Value;
+        use ::std::{boxed::Box, vec::Vec};
+        #[allow(unused_imports)]
+        use $crate::test_util::serde_json::json;
+        #[allow(clippy::needless_update)]
+        let config = $crate::test_util::TestServerConfig {
+            $( $field: $value.try_into().unwrap_or_else(|err| {
+                panic!("failed to convert {:?} into {}: {}", $value, stringify!($field), err);
+            }), )*
+            ..$crate::test_util::TestServerConfig::default()
+        };
+        let server = $crate::test_util::TEST_SERVER_POOL.get_server();
+        server.expect(
+            Expectation::matching(all_of![
+                request::method(config.method),
+                request::headers(all_of![
+                    contains(key(lowercase("x-api-key"))),
+                    contains((lowercase("user-agent"), matches("^mackerel-client-rs/"))),
+                    contains((lowercase("content-type"), "application/json")),
+                ]),
+                request::headers(all_of(
+                    config.headers
+                        .iter()
+                        .map(|&(name, value)| {
+                            Box::new(contains((eq(name.to_lowercase()), value))) as _
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+                request::path(config.path),
+                request::query(url_decoded(all_of(
+                    config.query_params
+                        .split_terminator('&')
+                        .map(|param| {
+                            Box::new(contains(param.split_once('=').unwrap_or((param, "")))) as _
+                        })
+                        .chain(::std::iter::once(
+                            Box::new(len(eq(config.query_params.split_terminator('&').count()))) as _
+                        ))
+                        .collect::<Vec<_>>(),
+                ))),
+                request::body(
+                    if config.request == Value::Null {
+                        all_of![""]
+                    } else {
+                        all_of![json_decoded(eq(config.request))]
+                    },
+                ),
+            ])
+            .times(1..)
+            .respond_with(
+                responders::status_code(config.status_code.as_u16())
+                    .append_header("Content-Type", "application/json")
+                    .body($crate::test_util::serde_json::to_string(&config.response).unwrap()),
+            ),
+        );
+        server
+    }};
+}
+
+/// Creates a client for the mock server started by [`test_server!`](crate::test_server),
+/// optionally with the API key.
+#[macro_export]
+macro_rules! test_client {
+    ($server:expr) => {
+        $crate::test_client!($server, "")
+    };
+    ($server:expr, $api_key:expr) => {
+        $crate::client::Client::builder()
+            .api_key($api_key)
+            .api_base($server.url_str("/"))
+            .build()
+    };
+}
+
+/// Canned entities for tests
+pub mod fixtures {
+    use chrono::DateTime;
+
+    use crate::alert::{Alert, AlertStatus, AlertValue};
+    use crate::channel::{Channel, ChannelValue, NotificationEvent};
+    use crate::dashboard::{Dashboard, DashboardValue};
+    use crate::downtime::{Downtime, DowntimeValue};
+    use crate::graph_annotation::{GraphAnnotation, GraphAnnotationValue};
+    use crate::host::{Host, HostStatus, HostValue};
+    use crate::monitor::{Monitor, MonitorOperator, MonitorType, MonitorValue};
+    use crate::organization::Organization;
+    use crate::role::Role;
+    use crate::service::Service;
+    use crate::user::{User, UserAuthority, UserValue};
+
+    /// Returns an organization `ExampleOrganization`.
+    pub fn organization() -> Organization {
+        Organization::builder().name("ExampleOrganization").build()
+    }
+
+    /// Returns a working host `host1` belonging to `service1:role1`.
+    pub fn host() -> Host {
+        Host::builder()
+            .id("host1")
+            .created_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+            .status(HostStatus::Working)
+            .roles([("service1".into(), vec!["role1".into()])])
+            .value(
+                HostValue::builder()
+                    .name("example-host")
+                    .role_fullnames(["service1:role1"])
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Returns a service `service1` with the role `role1`.
+    pub fn service() -> Service {
+        Service::builder()
+            .name("service1")
+            .memo("service memo")
+            .roles(["role1"])
+            .build()
+    }
+
+    /// Returns a role `role1`.
+    pub fn role() -> Role {
+        Role::builder().name("role1").memo("role memo").build()
+    }
+
+    /// Returns a host metric monitor `monitor1` scoped to `service1`.
+    pub fn monitor() -> Monitor {
+        Monitor::builder()
+            .id("monitor1")
+            .value(MonitorValue::Host {
+                name: "Example host monitor".to_string(),
+                memo: "Monitor memo".to_string(),
+                duration: 5,
+                metric: "loadavg5".to_string(),
+                operator: MonitorOperator::GreaterThan,
+                warning: Some(10.0),
+                critical: Some(20.0),
+                max_check_attempts: Some(5),
+                scopes: vec!["service1".into()],
+                exclude_scopes: vec![],
+                notification_interval: Some(30),
+                is_mute: false,
+                extra: Default::default(),
+            })
+            .build()
+    }
+
+    /// Returns an open critical alert `alert1` of `monitor1` on `host1`.
+    pub fn alert() -> Alert {
+        Alert::builder()
+            .id("alert1")
+            .value(
+                AlertValue::builder()
+                    .status(AlertStatus::Critical)
+                    .monitor_id("monitor1")
+                    .monitor_type(MonitorType::Host)
+                    .host_id("host1")
+                    .value(25.0)
+                    .opened_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Returns a downtime `downtime1`.
+    pub fn downtime() -> Downtime {
+        Downtime::builder()
+            .id("downtime1")
+            .value(
+                DowntimeValue::builder()
+                    .name("Example downtime")
+                    .memo("This is a downtime memo.")
+                    .start(DateTime::from_timestamp(1700000000, 0).unwrap())
+                    .duration(60)
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Returns a dashboard `dashboard1` without widgets.
+    pub fn dashboard() -> Dashboard {
+        Dashboard::builder()
+            .id("dashboard1")
+            .value(
+                DashboardValue::builder()
+                    .title("This is a dashboard")
+                    .memo("This is a dashboard memo.")
+                    .url_path("example")
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Returns a graph annotation `annotation1` of `service1`.
+    pub fn graph_annotation() -> GraphAnnotation {
+        GraphAnnotation::builder()
+            .id("annotation1")
+            .value(
+                GraphAnnotationValue::builder()
+                    .title("Deploy application")
+                    .from(DateTime::from_timestamp(1700000000, 0).unwrap())
+                    .to(DateTime::from_timestamp(1700000030, 0).unwrap())
+                    .service("service1")
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Returns an email channel `channel1`.
+    pub fn channel() -> Channel {
+        Channel::builder()
+            .id("channel1")
+            .value(ChannelValue::Email {
+                name: "Example Email Channel".to_string(),
+                suspended_at: None,
+                emails: vec!["mackerel@example.com".to_string()],
+                user_ids: vec!["user1".into()],
+                events: vec![NotificationEvent::Alert],
+            })
+            .build()
+    }
+
+    /// Returns a manager user `user1`.
+    pub fn user() -> User {
+        User::builder()
+            .id("user1")
+            .joined_at(DateTime::from_timestamp(1700000000, 0).unwrap())
+            .value(
+                UserValue::builder()
+                    .screen_name("Example User")
+                    .email("mackerel@example.com")
+                    .authority(UserAuthority::Manager)
+                    .build(),
+            )
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::fixtures;
+    #[cfg(feature = "reqwest")]
+    use crate::test_util::GET;
+
+    #[test]
+    fn fixtures() {
+        let value = serde_json::to_value(fixtures::host()).unwrap();
+        assert_eq!(serde_json::from_value(value).ok(), Some(fixtures::host()));
+        let value = serde_json::to_value(fixtures::monitor()).unwrap();
+        assert_eq!(
+            serde_json::from_value(value).ok(),
+            Some(fixtures::monitor())
+        );
+        let value = serde_json::to_value(fixtures::alert()).unwrap();
+        assert_eq!(serde_json::from_value(value).ok(), Some(fixtures::alert()));
+        let value = serde_json::to_value(fixtures::dashboard()).unwrap();
+        assert_eq!(
+            serde_json::from_value(value).ok(),
+            Some(fixtures::dashboard())
+        );
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[async_std::test]
+    async fn test_server_headers() {
+        let server = test_server! {
+            method = GET,
+            path = "/api/v0/monitors/monitor1",
+            headers = [("X-Api-Key", "<Mackerel-API-KEY>")],
+            response = json!({ "monitor": fixtures::monitor() }),
+        };
+        assert_eq!(
+            test_client!(server, "<Mackerel-API-KEY>")
+                .get_monitor("monitor1")
+                .await,
+            Ok(fixtures::monitor()),
+        );
+    }
+}
@@ -47,6 +47,10 @@ pub struct UserValue {
     pub screen_name: String,
     pub email: String,
     pub authority: UserAuthority,
+    /// The fields not supported by this library, preserved on update.
+    #[builder(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Authentication method
@@ -195,37 +199,41 @@ mod tests {
     }
 }
 
-impl Client {
-    /// Fetches all the users.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/users#list>.
-    pub async fn list_users(&self) -> Result<Vec<User>> {
-        self.request(
-            Method::GET,
-            "/api/v0/users",
-            query_params![],
-            request_body![],
-            response_body! { users: Vec<User> },
-        )
-        .await
-    }
+client_methods! {
+    impl Client {
+        /// Fetches all the users.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/users#list>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+        pub async fn list_users(&self) -> Result<Vec<User>> {
+            self.request(
+                Method::GET,
+                "/api/v0/users",
+                query_params![],
+                request_body![],
+                response_body! { users: Vec<User> },
+            )
+            .await
+        }
 
-    /// Delete the user from the organization.
-    ///
-    /// See <https://mackerel.io/api-docs/entry/users#delete>.
-    pub async fn delete_user(&self, user_id: impl Into<UserId>) -> Result<User> {
-        self.request(
-            Method::DELETE,
-            format_url!("/api/v0/users/{}", user_id),
-            query_params![],
-            request_body![],
-            response_body!(..),
-        )
-        .await
+        /// Delete the user from the organization.
+        ///
+        /// See <https://mackerel.io/api-docs/entry/users#delete>.
+        #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(user_id)))]
+        pub async fn delete_user(&self, user_id: impl Into<UserId>) -> Result<User> {
+            self.request(
+                Method::DELETE,
+                format_url!("/api/v0/users/{}", user_id),
+                query_params![],
+                request_body![],
+                response_body!(..),
+            )
+            .await
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod client_tests {
     use serde_json::json;
 